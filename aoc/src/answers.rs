@@ -0,0 +1,223 @@
+//! Known answers, stored as TOML keyed by day, input name and part:
+//!
+//! ```toml
+//! [day7.sample]
+//! part1 = "37"
+//! part2 = "168"
+//! ```
+use super::error::AocError;
+use super::input::Variant;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Outcome of checking an answer against the recorded one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Missing,
+}
+
+impl Verdict {
+    /// `pass`, `fail` or `missing`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Verdict::Pass => "pass",
+            Verdict::Fail { .. } => "fail",
+            Verdict::Missing => "missing",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    /// Answers by day, input name and part.
+    answers: BTreeMap<(u8, String, u8), String>,
+}
+
+impl Answers {
+    pub fn new() -> Self {
+        Answers {
+            answers: BTreeMap::new(),
+        }
+    }
+
+    /// Path of the answers file checked into the repository.
+    pub fn bundled_path() -> PathBuf {
+        PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/answers.toml"))
+    }
+
+    /// Loads answers from `path`, a missing file has no answers.
+    pub fn load(path: &Path) -> Result<Self, AocError> {
+        if !path.exists() {
+            return Ok(Answers::new());
+        }
+        let text = std::fs::read_to_string(path).map_err(|e| AocError::Io {
+            path: path.to_path_buf(),
+            message: e.to_string(),
+        })?;
+        Answers::parse(&text).map_err(|message| AocError::Config {
+            path: path.to_path_buf(),
+            message,
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), AocError> {
+        std::fs::write(path, self.to_string()).map_err(|e| AocError::Io {
+            path: path.to_path_buf(),
+            message: e.to_string(),
+        })
+    }
+
+    fn parse(text: &str) -> Result<Self, String> {
+        let table = text.parse::<toml::Table>().map_err(|e| e.to_string())?;
+        let mut result = Answers::new();
+        for (day_key, inputs) in &table {
+            let day = day_key
+                .strip_prefix("day")
+                .and_then(|d| d.parse::<u8>().ok())
+                .ok_or(format!("{}: expected dayN", day_key))?;
+            let inputs = inputs
+                .as_table()
+                .ok_or(format!("{}: expected table of inputs", day_key))?;
+            for (input, parts) in inputs {
+                let parts = parts
+                    .as_table()
+                    .ok_or(format!("{}.{}: expected table of parts", day_key, input))?;
+                for (part_key, answer) in parts {
+                    let key = format!("{}.{}.{}", day_key, input, part_key);
+                    let part = match part_key.as_str() {
+                        "part1" => 1,
+                        "part2" => 2,
+                        _ => return Err(format!("{}: expected part1 or part2", key)),
+                    };
+                    let answer = answer
+                        .as_str()
+                        .ok_or(format!("{}: answer must be a string", key))?;
+                    result
+                        .answers
+                        .insert((day, input.clone(), part), answer.to_string());
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    pub fn get(&self, day: u8, part: u8, input: &Variant) -> Option<&str> {
+        self.answers
+            .get(&(day, input.to_string(), part))
+            .map(|a| a.as_str())
+    }
+
+    /// Names of the inputs of `day` that have answers.
+    pub fn inputs(&self, day: u8) -> Vec<Variant> {
+        let mut names: Vec<&str> = self
+            .answers
+            .keys()
+            .filter(|(d, _, _)| *d == day)
+            .map(|(_, name, _)| name.as_str())
+            .collect();
+        names.dedup();
+        names.into_iter().filter_map(|n| n.parse().ok()).collect()
+    }
+
+    pub fn record(&mut self, day: u8, part: u8, input: &Variant, answer: &str) {
+        self.answers.insert(
+            (day, input.to_string(), part),
+            answer.trim_end().to_string(),
+        );
+    }
+
+    /// Compares `answer` with the recorded one, ignoring trailing whitespace.
+    pub fn check(&self, day: u8, part: u8, input: &Variant, answer: &str) -> Verdict {
+        match self.get(day, part, input) {
+            None => Verdict::Missing,
+            Some(expected) if expected == answer.trim_end() => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.to_string(),
+            },
+        }
+    }
+}
+
+/// Writes the answers as TOML, with days in numeric order.
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut previous: Option<(u8, &str)> = None;
+        for ((day, input, part), answer) in &self.answers {
+            if previous != Some((*day, input)) {
+                if previous.is_some() {
+                    writeln!(f)?;
+                }
+                if input
+                    .chars()
+                    .all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '-')
+                {
+                    writeln!(f, "[day{}.{}]", day, input)?;
+                } else {
+                    writeln!(f, "[day{}.{}]", day, toml::Value::from(input.as_str()))?;
+                }
+                previous = Some((*day, input));
+            }
+            writeln!(f, "part{} = {}", part, toml::Value::from(answer.as_str()))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::bundled;
+    use crate::registry::registry;
+
+    #[test]
+    fn check_test() {
+        let mut answers = Answers::new();
+        answers.record(7, 1, &Variant::sample(), "37\n");
+        assert_eq!(answers.check(7, 1, &Variant::sample(), "37"), Verdict::Pass);
+        assert_eq!(
+            answers.check(7, 1, &Variant::sample(), "38"),
+            Verdict::Fail {
+                expected: "37".to_string()
+            }
+        );
+        assert_eq!(answers.check(7, 1, &Variant::Real, "37"), Verdict::Missing);
+        assert_eq!(answers.inputs(7), vec![Variant::sample()]);
+    }
+
+    #[test]
+    fn round_trip_test() {
+        let mut answers = Answers::new();
+        answers.record(10, 2, &Variant::Real, "288957");
+        answers.record(7, 1, &Variant::sample(), "37");
+        answers.record(13, 2, &Variant::sample(), "#####\n#...#");
+        let text = answers.to_string();
+        assert!(text.starts_with("[day7.sample]\npart1 = \"37\"\n"));
+        assert_eq!(Answers::parse(&text).unwrap(), answers);
+
+        assert!(Answers::parse("[day7.sample]\npart3 = \"1\"").is_err());
+    }
+
+    /// Every recorded sample answer of the enabled days still holds.
+    #[test]
+    fn samples_test() {
+        let answers = Answers::load(&Answers::bundled_path()).unwrap();
+        for ((day, input, part), expected) in &answers.answers {
+            let solution = match registry().get(*day, *part) {
+                Some(solution) if input != "real" => solution,
+                _ => continue,
+            };
+            let answer = solution.run(&bundled(*day, input)).unwrap();
+            assert_eq!(
+                answer.trim_end(),
+                expected,
+                "day {} part {} {}",
+                day,
+                part,
+                input
+            );
+        }
+    }
+}
@@ -0,0 +1,489 @@
+//! Cellular automata on a [VecField]: puzzles supply the rule, the engine
+//! does the stepping.
+//!
+//! An [Automaton] computes the next value of every cell from its
+//! [Neighborhood] at once, into a second buffer that is swapped in after the
+//! step. It is either bounded by its field, or infinite with every cell
+//! outside of the field having the background value, in which case the field
+//! grows by one cell on each side per step.
+//!
+//! A [Cascade] charges every cell, and cells that fire charge their neighbors
+//! in turn, until nothing fires any more.
+//!
+//! Both are iterators over steps, see [Evolve].
+use super::points::*;
+use super::vec_field::VecField;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A cell and the cells around it, as seen by a [Rule].
+pub struct Neighborhood<'a, T> {
+    field: &'a VecField<T>,
+    background: Option<&'a T>,
+    center: Point,
+    connectivity: Connectivity,
+}
+
+impl<'a, T> Neighborhood<'a, T> {
+    /// Cells around `center`, with `background` outside of `field` unless
+    /// it is `None`.
+    pub fn new(
+        field: &'a VecField<T>,
+        background: Option<&'a T>,
+        center: Point,
+        connectivity: Connectivity,
+    ) -> Self {
+        Neighborhood {
+            field,
+            background,
+            center,
+            connectivity,
+        }
+    }
+
+    /// Value of the cell at `offset` from the center, `None` if it is outside
+    /// of a bounded field.
+    pub fn get(&self, offset: Offset) -> Option<&'a T> {
+        self.field.get(&(self.center + offset)).or(self.background)
+    }
+
+    /// Value of the center cell.
+    pub fn value(&self) -> &'a T {
+        self.get(Offset::default())
+            .expect("center of a neighborhood is outside of the field")
+    }
+
+    /// Values of the neighbors in reading order, skipping cells outside of a
+    /// bounded field.
+    pub fn neighbors(&self) -> impl Iterator<Item = &'a T> + '_ {
+        let offsets: &'static [Offset] = match self.connectivity {
+            Connectivity::Orthogonal => &ORTHOGONAL,
+            Connectivity::All => &ALL_AROUND,
+        };
+        offsets.iter().filter_map(|offset| self.get(*offset))
+    }
+
+    /// Number of neighbors with values matching `predicate`.
+    pub fn count(&self, predicate: impl Fn(&T) -> bool) -> usize {
+        self.neighbors().filter(|v| predicate(v)).count()
+    }
+
+    /// The 3x3 square around the center in reading order, the center
+    /// included.
+    pub fn window(&self) -> impl Iterator<Item = Option<&'a T>> + '_ {
+        (-1..=1).flat_map(move |y| (-1..=1).map(move |x| self.get(Offset::new(x, y))))
+    }
+}
+
+/// Computes the next value of a cell.
+pub trait Rule<T> {
+    fn next(&self, cells: &Neighborhood<T>) -> T;
+}
+
+impl<T, F: Fn(&Neighborhood<T>) -> T> Rule<T> for F {
+    fn next(&self, cells: &Neighborhood<T>) -> T {
+        self(cells)
+    }
+}
+
+/// A cycle found by [Evolve::find_cycle]: the state at generation `start`
+/// repeats every `period` steps.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub period: usize,
+}
+
+impl Cycle {
+    /// Whether the automaton stopped changing.
+    pub fn is_steady(&self) -> bool {
+        self.period == 1
+    }
+}
+
+/// An automaton that steps as an iterator, yielding the number of cells
+/// that changed or fired in each step.
+pub trait Evolve: Iterator<Item = usize> {
+    /// What identifies the state of the automaton between steps.
+    type State: Hash + Eq;
+
+    fn state(&self) -> Self::State;
+
+    /// Number of steps done.
+    fn generation(&self) -> usize;
+
+    /// Steps until a state repeats, at most `max_steps` times.
+    fn find_cycle(&mut self, max_steps: usize) -> Option<Cycle> {
+        let mut seen = HashMap::new();
+        for step in 0..=max_steps {
+            if let Some(start) = seen.insert(self.state(), self.generation()) {
+                return Some(Cycle {
+                    start,
+                    period: self.generation() - start,
+                });
+            }
+            if step < max_steps {
+                self.next();
+            }
+        }
+        None
+    }
+}
+
+pub struct Automaton<T, R> {
+    field: VecField<T>,
+    buffer: VecField<T>,
+    background: Option<T>,
+    /// Position of `0,0` of the field.
+    origin: Point,
+    connectivity: Connectivity,
+    rule: R,
+    generation: usize,
+}
+
+impl<T: Clone + PartialEq, R: Rule<T>> Automaton<T, R> {
+    /// Automaton whose cells are the cells of `field`.
+    pub fn bounded(field: VecField<T>, connectivity: Connectivity, rule: R) -> Self {
+        Automaton {
+            buffer: field.clone(),
+            field,
+            background: None,
+            origin: Point::default(),
+            connectivity,
+            rule,
+            generation: 0,
+        }
+    }
+
+    /// Automaton on an infinite grid of `background` cells, with `field` at
+    /// `0,0`.
+    pub fn infinite(
+        field: VecField<T>,
+        background: T,
+        connectivity: Connectivity,
+        rule: R,
+    ) -> Self {
+        Automaton {
+            background: Some(background),
+            ..Automaton::bounded(field, connectivity, rule)
+        }
+    }
+
+    /// Cells that may differ from the background.
+    pub fn field(&self) -> &VecField<T> {
+        &self.field
+    }
+
+    /// Value of the cells outside of the field of an infinite automaton.
+    pub fn background(&self) -> Option<&T> {
+        self.background.as_ref()
+    }
+
+    /// Position of the top left cell of the field, which moves as an
+    /// infinite field grows.
+    pub fn origin(&self) -> Point {
+        self.origin
+    }
+
+    fn neighborhood(&self, center: Point) -> Neighborhood<'_, T> {
+        Neighborhood::new(
+            &self.field,
+            self.background.as_ref(),
+            center,
+            self.connectivity,
+        )
+    }
+
+    /// Applies the rule to every cell, returns the number of cells that
+    /// changed.
+    pub fn step(&mut self) -> usize {
+        let margin = if self.background.is_some() { 1 } else { 0 };
+        let shift = Offset::new(margin, margin);
+        self.buffer.width = self.field.width + 2 * margin as u32;
+        self.buffer.height = self.field.height + 2 * margin as u32;
+        self.buffer.values.clear();
+        let mut changed = 0;
+        for y in 0..self.buffer.height as i32 {
+            for x in 0..self.buffer.width as i32 {
+                let cells = self.neighborhood(Point::new(x, y) - shift);
+                let next = self.rule.next(&cells);
+                if next != *cells.value() {
+                    changed += 1;
+                }
+                self.buffer.values.push(next);
+            }
+        }
+        if self.background.is_some() {
+            // Two cells away from the field, all neighbors are background.
+            let next = self.rule.next(&self.neighborhood(Point::new(-2, -2)));
+            self.background = Some(next);
+            self.origin -= shift;
+        }
+        std::mem::swap(&mut self.field, &mut self.buffer);
+        self.generation += 1;
+        changed
+    }
+
+    pub fn into_field(self) -> VecField<T> {
+        self.field
+    }
+}
+
+impl<T: Clone + PartialEq, R: Rule<T>> Iterator for Automaton<T, R> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        Some(self.step())
+    }
+}
+
+impl<T: Clone + Hash + Eq, R: Rule<T>> Evolve for Automaton<T, R> {
+    /// Position and values of the cells that differ from the background, and
+    /// the background.
+    type State = (Option<Point>, VecField<T>, Option<T>);
+
+    fn state(&self) -> Self::State {
+        let bounds = match &self.background {
+            Some(background) => Rect::from_points(
+                self.field
+                    .iter()
+                    .filter(|(_, v)| *v != background)
+                    .map(|(p, _)| p),
+            ),
+            None => self.field.bounds(),
+        };
+        match bounds {
+            Some(bounds) => (
+                Some(self.origin + bounds.min),
+                self.field.crop(&bounds).1,
+                self.background.clone(),
+            ),
+            None => (
+                None,
+                VecField {
+                    width: 0,
+                    height: 0,
+                    values: Vec::new(),
+                },
+                self.background.clone(),
+            ),
+        }
+    }
+
+    fn generation(&self) -> usize {
+        self.generation
+    }
+}
+
+/// How the cells of a [Cascade] charge and fire.
+pub trait CascadeRule<T> {
+    /// Charges a cell at the start of a step, returns whether it fires.
+    fn charge(&self, cell: &mut T) -> bool;
+
+    /// Charges a cell that did not fire yet from a neighbor that fired,
+    /// returns whether it fires.
+    fn spread(&self, cell: &mut T) -> bool {
+        self.charge(cell)
+    }
+
+    /// Updates a cell that fired at the end of the step.
+    fn reset(&self, cell: &mut T);
+}
+
+pub struct Cascade<T, R> {
+    field: VecField<T>,
+    fired: VecField<bool>,
+    queue: Vec<Point>,
+    connectivity: Connectivity,
+    rule: R,
+    generation: usize,
+}
+
+impl<T, R: CascadeRule<T>> Cascade<T, R> {
+    pub fn new(field: VecField<T>, connectivity: Connectivity, rule: R) -> Self {
+        Cascade {
+            fired: field.map(|_| false),
+            field,
+            queue: Vec::new(),
+            connectivity,
+            rule,
+            generation: 0,
+        }
+    }
+
+    pub fn field(&self) -> &VecField<T> {
+        &self.field
+    }
+
+    /// Charges every cell and spreads charge from the cells that fire,
+    /// returns the number of cells that fired.
+    pub fn step(&mut self) -> usize {
+        self.fired.values.fill(false);
+        for (point, cell) in self.field.points().zip(self.field.values.iter_mut()) {
+            if self.rule.charge(cell) {
+                self.fired[point] = true;
+                self.queue.push(point);
+            }
+        }
+        while let Some(point) = self.queue.pop() {
+            for neighbor in self.field.neighbors(point, self.connectivity) {
+                if !self.fired[neighbor] && self.rule.spread(&mut self.field[neighbor]) {
+                    self.fired[neighbor] = true;
+                    self.queue.push(neighbor);
+                }
+            }
+        }
+        let mut count = 0;
+        for (cell, fired) in self.field.values.iter_mut().zip(&self.fired.values) {
+            if *fired {
+                self.rule.reset(cell);
+                count += 1;
+            }
+        }
+        self.generation += 1;
+        count
+    }
+
+    pub fn into_field(self) -> VecField<T> {
+        self.field
+    }
+}
+
+impl<T, R: CascadeRule<T>> Iterator for Cascade<T, R> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        Some(self.step())
+    }
+}
+
+impl<T: Clone + Hash + Eq, R: CascadeRule<T>> Evolve for Cascade<T, R> {
+    type State = VecField<T>;
+
+    fn state(&self) -> VecField<T> {
+        self.field.clone()
+    }
+
+    fn generation(&self) -> usize {
+        self.generation
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn life(cells: &Neighborhood<bool>) -> bool {
+        matches!(
+            (*cells.value(), cells.count(|alive| *alive)),
+            (true, 2) | (_, 3)
+        )
+    }
+
+    fn parse(text: &str) -> VecField<bool> {
+        VecField::parse(text, "'#' or '.'", |ch| Some(ch == '#')).unwrap()
+    }
+
+    fn show(field: &VecField<bool>) -> String {
+        field.map(|v| if *v { '#' } else { '.' }).to_string()
+    }
+
+    #[test]
+    fn bounded_test() {
+        let mut blinker = Automaton::bounded(parse("...\n###\n...\n"), Connectivity::All, life);
+        assert_eq!(blinker.step(), 4);
+        assert_eq!(show(blinker.field()), ".#.\n.#.\n.#.\n");
+        assert_eq!(
+            blinker.find_cycle(10),
+            Some(Cycle {
+                start: 1,
+                period: 2
+            })
+        );
+
+        let mut block = Automaton::bounded(parse("##\n##\n"), Connectivity::All, life);
+        assert_eq!(block.next(), Some(0));
+        assert!(block.find_cycle(10).unwrap().is_steady());
+    }
+
+    #[test]
+    fn infinite_test() {
+        let mut glider =
+            Automaton::infinite(parse(".#.\n..#\n###\n"), false, Connectivity::All, life);
+        let start = glider.state();
+        glider.by_ref().take(4).for_each(drop);
+        assert_eq!(glider.field().width, 11);
+        assert_eq!(glider.origin(), Point::new(-4, -4));
+        let (position, cells, background) = glider.state();
+        assert_eq!(position, start.0.map(|p| p + Offset::new(1, 1)));
+        assert_eq!((cells, background), (start.1, start.2));
+        // A glider moves, so its states never repeat.
+        assert_eq!(glider.find_cycle(8), None);
+
+        // Every cell flips, the background included.
+        let mut flip = Automaton::infinite(
+            parse("#"),
+            false,
+            Connectivity::All,
+            |cells: &Neighborhood<bool>| !cells.value(),
+        );
+        flip.step();
+        assert_eq!(flip.background(), Some(&true));
+        assert_eq!(show(flip.field()), "###\n#.#\n###\n");
+        assert_eq!(
+            flip.find_cycle(4),
+            Some(Cycle {
+                start: 1,
+                period: 2
+            })
+        );
+    }
+
+    #[test]
+    fn window_test() {
+        let field = parse("#.\n.#\n");
+        let cells = Neighborhood::new(&field, None, Point::new(0, 0), Connectivity::Orthogonal);
+        let window: Vec<Option<bool>> = cells.window().map(|v| v.copied()).collect();
+        assert_eq!(
+            window,
+            vec![
+                None,
+                None,
+                None,
+                None,
+                Some(true),
+                Some(false),
+                None,
+                Some(false),
+                Some(true)
+            ]
+        );
+        assert_eq!(cells.neighbors().count(), 2);
+    }
+
+    struct Charge;
+
+    impl CascadeRule<u8> for Charge {
+        fn charge(&self, cell: &mut u8) -> bool {
+            *cell += 1;
+            *cell > 2
+        }
+
+        fn reset(&self, cell: &mut u8) {
+            *cell = 0;
+        }
+    }
+
+    #[test]
+    fn cascade_test() {
+        let field = VecField::parse("100\n000\n002\n", "digit", |ch| {
+            ch.to_digit(10).map(|d| d as u8)
+        })
+        .unwrap();
+        let mut cascade = Cascade::new(field, Connectivity::All, Charge);
+        assert_eq!(cascade.step(), 1);
+        assert_eq!(cascade.field().to_string(), "211\n122\n120\n");
+        let cycle = cascade.find_cycle(100).unwrap();
+        assert_eq!(cascade.generation() - cycle.start, cycle.period);
+    }
+}
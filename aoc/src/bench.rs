@@ -0,0 +1,337 @@
+//! Benchmarks of registered parts: every part is parsed and solved a number
+//! of times after a warm-up, and parse and solve times are summarized
+//! separately.
+//!
+//! Results can be saved as a named baseline and later runs compared against
+//! it to find regressions.
+use super::error::AocError;
+use super::registry::Part;
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Copy, Debug)]
+pub struct BenchOptions {
+    /// Runs that are not measured.
+    pub warmup: usize,
+    /// Measured runs, at least 1.
+    pub runs: usize,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        BenchOptions {
+            warmup: 3,
+            runs: 10,
+        }
+    }
+}
+
+/// Summary of the durations of several runs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    pub fn of(mut samples: Vec<Duration>) -> Stats {
+        assert!(!samples.is_empty(), "No samples to summarize");
+        samples.sort();
+        let n = samples.len();
+        let median = if n % 2 == 1 {
+            samples[n / 2]
+        } else {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        };
+        // Nearest-rank percentile.
+        let p95 = samples[(n * 95).div_ceil(100) - 1];
+        Stats {
+            runs: n,
+            min: samples[0],
+            median,
+            p95,
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "runs": self.runs,
+            "min_ns": self.min.as_nanos() as u64,
+            "median_ns": self.median.as_nanos() as u64,
+            "p95_ns": self.p95.as_nanos() as u64,
+        })
+    }
+
+    fn from_json(value: &Value) -> Option<Stats> {
+        let nanos = |key: &str| value[key].as_u64().map(Duration::from_nanos);
+        Some(Stats {
+            runs: value["runs"].as_u64()? as usize,
+            min: nanos("min_ns")?,
+            median: nanos("median_ns")?,
+            p95: nanos("p95_ns")?,
+        })
+    }
+}
+
+/// Timings of one part on one input.
+#[derive(Clone, Debug)]
+pub struct PartBench {
+    pub day: u8,
+    pub part: u8,
+    pub input: String,
+    /// Name of the implementation of the part.
+    pub implementation: String,
+    pub parse: Stats,
+    pub solve: Stats,
+}
+
+impl PartBench {
+    pub fn to_json(&self) -> Value {
+        json!({
+            "day": self.day,
+            "part": self.part,
+            "input": self.input,
+            "impl": self.implementation,
+            "parse": self.parse.to_json(),
+            "solve": self.solve.to_json(),
+        })
+    }
+
+    fn from_json(value: &Value) -> Option<PartBench> {
+        Some(PartBench {
+            day: value["day"].as_u64()? as u8,
+            part: value["part"].as_u64()? as u8,
+            input: value["input"].as_str()?.to_string(),
+            implementation: value["impl"].as_str()?.to_string(),
+            parse: Stats::from_json(&value["parse"])?,
+            solve: Stats::from_json(&value["solve"])?,
+        })
+    }
+}
+
+fn time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+/// Benchmarks `part` on `text`, the input named `input`. Panics if
+/// `options.runs` is 0.
+pub fn bench(
+    part: &dyn Part,
+    input: &str,
+    text: &str,
+    options: &BenchOptions,
+) -> Result<PartBench, AocError> {
+    let mut parse_times = Vec::with_capacity(options.runs);
+    let mut parsed = None;
+    for i in 0..options.warmup + options.runs {
+        let (result, elapsed) = time(|| part.parse(text));
+        parsed = Some(result?);
+        if i >= options.warmup {
+            parse_times.push(elapsed);
+        }
+    }
+    let parsed = parsed.unwrap();
+
+    let mut solve_times = Vec::with_capacity(options.runs);
+    for i in 0..options.warmup + options.runs {
+        let (_, elapsed) = time(|| part.solve(parsed.as_ref()));
+        if i >= options.warmup {
+            solve_times.push(elapsed);
+        }
+    }
+
+    Ok(PartBench {
+        day: part.day(),
+        part: part.part(),
+        input: input.to_string(),
+        implementation: part.name().to_string(),
+        parse: Stats::of(parse_times),
+        solve: Stats::of(solve_times),
+    })
+}
+
+/// All results of a benchmark run, as written with `--json`.
+pub fn to_json(options: &BenchOptions, results: &[PartBench]) -> Value {
+    json!({
+        "warmup": options.warmup,
+        "runs": options.runs,
+        "results": results.iter().map(PartBench::to_json).collect::<Vec<Value>>(),
+    })
+}
+
+/// Parses results written by [to_json].
+pub fn from_json(value: &Value) -> Option<Vec<PartBench>> {
+    value["results"]
+        .as_array()?
+        .iter()
+        .map(PartBench::from_json)
+        .collect()
+}
+
+/// Directory baselines are saved to.
+pub fn baseline_dir() -> PathBuf {
+    PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/../target/baselines"))
+}
+
+fn baseline_path(dir: &Path, name: &str) -> PathBuf {
+    dir.join(format!("{}.json", name))
+}
+
+pub fn save_baseline(
+    dir: &Path,
+    name: &str,
+    options: &BenchOptions,
+    results: &[PartBench],
+) -> Result<(), AocError> {
+    let path = baseline_path(dir, name);
+    std::fs::create_dir_all(dir)
+        .and_then(|_| std::fs::write(&path, format!("{:#}\n", to_json(options, results))))
+        .map_err(|e| AocError::Io {
+            path,
+            message: e.to_string(),
+        })
+}
+
+pub fn load_baseline(dir: &Path, name: &str) -> Result<Vec<PartBench>, AocError> {
+    let path = baseline_path(dir, name);
+    let text = std::fs::read_to_string(&path).map_err(|e| AocError::Io {
+        path: path.clone(),
+        message: e.to_string(),
+    })?;
+    serde_json::from_str::<Value>(&text)
+        .ok()
+        .as_ref()
+        .and_then(from_json)
+        .ok_or(AocError::Config {
+            path,
+            message: "not a benchmark baseline".to_string(),
+        })
+}
+
+/// A median time that got slower than the baseline allows.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Regression {
+    pub day: u8,
+    pub part: u8,
+    pub input: String,
+    pub implementation: String,
+    /// `parse` or `solve`.
+    pub phase: &'static str,
+    pub baseline: Duration,
+    pub current: Duration,
+}
+
+impl Regression {
+    /// Relative change to the baseline in percent.
+    pub fn change(&self) -> f64 {
+        (self.current.as_secs_f64() / self.baseline.as_secs_f64() - 1.0) * 100.0
+    }
+}
+
+/// Compares median parse and solve times of the implementations of parts
+/// present in both runs.
+/// A time regressed if it is more than `tolerance` percent slower than the
+/// baseline.
+pub fn compare(baseline: &[PartBench], current: &[PartBench], tolerance: f64) -> Vec<Regression> {
+    let mut result = Vec::new();
+    for c in current {
+        let b = match baseline.iter().find(|b| {
+            (b.day, b.part, &b.input, &b.implementation)
+                == (c.day, c.part, &c.input, &c.implementation)
+        }) {
+            Some(b) => b,
+            None => continue,
+        };
+        for (phase, baseline, current) in [
+            ("parse", b.parse.median, c.parse.median),
+            ("solve", b.solve.median, c.solve.median),
+        ] {
+            if current.as_secs_f64() > baseline.as_secs_f64() * (1.0 + tolerance / 100.0) {
+                result.push(Regression {
+                    day: c.day,
+                    part: c.part,
+                    input: c.input.clone(),
+                    implementation: c.implementation.clone(),
+                    phase,
+                    baseline,
+                    current,
+                });
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_test() {
+        let stats = Stats::of((1..=20).rev().map(Duration::from_millis).collect());
+        assert_eq!(stats.runs, 20);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_micros(10500));
+        assert_eq!(stats.p95, Duration::from_millis(19));
+
+        let stats = Stats::of(vec![Duration::from_millis(3)]);
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.p95, Duration::from_millis(3));
+    }
+
+    #[test]
+    #[cfg(feature = "day7")]
+    fn bench_test() {
+        let options = BenchOptions { warmup: 1, runs: 5 };
+        let part = crate::registry::registry().get(7, 2).unwrap();
+        let result = bench(part, "sample", "16,1,2,0,4,2,7,1,2,14", &options).unwrap();
+        assert_eq!(result.solve.runs, 5);
+        assert!(result.parse.min <= result.parse.p95);
+
+        let json = to_json(&options, &[result]);
+        assert_eq!(json["results"][0]["day"], 7);
+        assert_eq!(json["results"][0]["input"], "sample");
+        assert_eq!(json["results"][0]["impl"], "default");
+        assert!(json["results"][0]["solve"]["median_ns"].is_u64());
+        assert!(bench(part, "bad", "x", &options).is_err());
+    }
+
+    fn result(day: u8, parse_ms: u64, solve_ms: u64) -> PartBench {
+        PartBench {
+            day,
+            part: 1,
+            input: "real".to_string(),
+            implementation: "default".to_string(),
+            parse: Stats::of(vec![Duration::from_millis(parse_ms)]),
+            solve: Stats::of(vec![Duration::from_millis(solve_ms)]),
+        }
+    }
+
+    #[test]
+    fn baseline_test() {
+        let options = BenchOptions::default();
+        let baseline = vec![result(15, 10, 100), result(19, 10, 1000)];
+        let dir = std::env::temp_dir().join(format!("aoc-baselines-{}", std::process::id()));
+        save_baseline(&dir, "main", &options, &baseline).unwrap();
+        let loaded = load_baseline(&dir, "main").unwrap();
+        assert_eq!(loaded[1].solve, baseline[1].solve);
+        assert!(load_baseline(&dir, "other").is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let current = vec![result(15, 10, 109), result(19, 20, 1200), result(22, 1, 1)];
+        let regressions = compare(&loaded, &current, 10.0);
+        assert_eq!(
+            regressions
+                .iter()
+                .map(|r| (r.day, r.phase))
+                .collect::<Vec<_>>(),
+            vec![(19, "parse"), (19, "solve")]
+        );
+        assert_eq!(regressions[1].change().round(), 20.0);
+    }
+}
@@ -0,0 +1,606 @@
+use aoc::answers::{Answers, Verdict};
+use aoc::bench::{bench, BenchOptions, PartBench, Regression};
+use aoc::error::AocError;
+use aoc::input::{InputProvider, Variant};
+use aoc::output::Filter;
+use aoc::registry::{registry, CrossCheck, Part};
+use aoc::runner::{run_parallel, PartRun, TSV_HEADER};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use std::io::Read;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Advent of Code 2021 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+
+    /// What to log to stderr, for example `day22=debug,day17=trace`. Nothing
+    /// is logged by default.
+    #[arg(long, global = true, env = "AOC_LOG", value_name = "FILTER")]
+    log: Option<Filter>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Runs a day against an input file and prints the answers.
+    Run(RunArgs),
+    /// Runs solutions and checks their answers against the answers file.
+    Verify(VerifyArgs),
+    /// Measures parse and solve times of registered parts.
+    Bench(BenchArgs),
+    /// Lists the days that have solutions.
+    List,
+}
+
+/// How `run` prints its results.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// Answers for people to read.
+    Human,
+    /// A JSON object per line for each part.
+    Json,
+    /// Tab separated values with a header line.
+    Tsv,
+}
+
+#[derive(Args)]
+struct RunArgs {
+    #[arg(long, required_unless_present = "all")]
+    day: Option<u8>,
+
+    /// Run every day instead of a single one.
+    #[arg(long, conflicts_with_all = ["day", "input"])]
+    all: bool,
+
+    /// Number of threads for `--all`, one per CPU if omitted.
+    #[arg(long, conflicts_with = "day")]
+    jobs: Option<usize>,
+
+    /// Part to run, both parts if omitted.
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    /// Input file, `-` to read from stdin. Defaults to the day's input in
+    /// the input directory.
+    #[arg(long)]
+    input: Option<PathBuf>,
+
+    /// Name of the sample to load from the input directory instead of the
+    /// real input.
+    #[arg(long, conflicts_with = "input")]
+    sample: Option<Variant>,
+
+    /// Print parse and solve times to stderr.
+    #[arg(long)]
+    time: bool,
+
+    /// Implementation to run instead of the default one. Parts without an
+    /// implementation of this name are skipped.
+    #[arg(long = "impl", value_name = "NAME")]
+    implementation: Option<String>,
+
+    /// Print a record per part with the answer, its type, the input, parse
+    /// and solve times and the verification status.
+    #[arg(long, value_enum, default_value_t = Format::Human)]
+    format: Format,
+}
+
+#[derive(Args)]
+struct VerifyArgs {
+    /// Day to verify, all days if omitted.
+    #[arg(long)]
+    day: Option<u8>,
+
+    /// Sample to verify in addition to the real input and the inputs that
+    /// already have answers. May be repeated.
+    #[arg(long)]
+    sample: Vec<Variant>,
+
+    /// Answers file, defaults to the one in the repository.
+    #[arg(long)]
+    answers: Option<PathBuf>,
+
+    /// Record answers of parts that have none yet.
+    #[arg(long)]
+    record: bool,
+
+    /// Also run every other implementation of each part and fail if they
+    /// disagree.
+    #[arg(long)]
+    cross_check: bool,
+}
+
+#[derive(Args)]
+struct BenchArgs {
+    /// Day to benchmark, all days if omitted.
+    #[arg(long)]
+    day: Option<u8>,
+
+    /// Part to benchmark, both parts if omitted.
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    /// Name of the sample to benchmark instead of the real input.
+    #[arg(long)]
+    sample: Option<Variant>,
+
+    /// Implementation to benchmark instead of the default one. Parts
+    /// without an implementation of this name are skipped.
+    #[arg(long = "impl", value_name = "NAME")]
+    implementation: Option<String>,
+
+    /// Number of measured runs, at least 1.
+    #[arg(
+        long,
+        default_value_t = BenchOptions::default().runs as u64,
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    runs: u64,
+
+    /// Number of runs before measuring.
+    #[arg(long, default_value_t = BenchOptions::default().warmup)]
+    warmup: usize,
+
+    /// Write the results as JSON to this file, `-` for stdout.
+    #[arg(long)]
+    json: Option<PathBuf>,
+
+    /// Save the results as a baseline with this name.
+    #[arg(long, value_name = "NAME")]
+    save_baseline: Option<String>,
+
+    /// Compare the results with the baseline of this name and fail if any
+    /// median time regressed.
+    #[arg(long, value_name = "NAME")]
+    baseline: Option<String>,
+
+    /// Allowed slowdown compared to the baseline, in percent.
+    #[arg(long, default_value_t = 10.0)]
+    tolerance: f64,
+
+    /// Directory of saved baselines, defaults to `target/baselines`.
+    #[arg(long)]
+    baseline_dir: Option<PathBuf>,
+}
+
+fn read_input(day: u8, args: &RunArgs) -> Result<String, AocError> {
+    match &args.input {
+        Some(path) if path.as_os_str() == "-" => {
+            let mut text = String::new();
+            std::io::stdin()
+                .read_to_string(&mut text)
+                .map_err(|e| AocError::Io {
+                    path: path.clone(),
+                    message: e.to_string(),
+                })?;
+            Ok(text)
+        }
+        Some(path) => std::fs::read_to_string(path).map_err(|e| AocError::Io {
+            path: path.clone(),
+            message: e.to_string(),
+        }),
+        None => {
+            let variant = args.sample.clone().unwrap_or(Variant::Real);
+            InputProvider::from_env()?.load(day, &variant)
+        }
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.3}ms", duration.as_secs_f64() * 1000.0)
+}
+
+/// Parts matching the filters, with the implementation called `name` or the
+/// default one.
+fn select_parts(day: Option<u8>, part: Option<u8>, name: Option<&str>) -> Vec<&'static dyn Part> {
+    registry()
+        .parts()
+        .filter(|p| day.is_none_or(|day| p.day() == day))
+        .filter(|p| part.is_none_or(|part| p.part() == part))
+        .filter_map(|p| match name {
+            Some(name) => registry().get_impl(p.day(), p.part(), name),
+            None => Some(p),
+        })
+        .collect()
+}
+
+/// Error for a day that has no parts to run.
+fn no_parts(day: u8, part: Option<u8>, name: Option<&str>) -> AocError {
+    match name {
+        Some(name) if registry().get(day, part.unwrap_or(1)).is_some() => {
+            AocError::NoImplementation {
+                day,
+                part,
+                name: name.to_string(),
+            }
+        }
+        _ => AocError::NoSolution { day, part },
+    }
+}
+
+fn run(args: &RunArgs) -> Result<(), AocError> {
+    let day = match args.day {
+        Some(day) => day,
+        None => return run_all(args),
+    };
+    let name = args.implementation.as_deref();
+    let parts = select_parts(Some(day), args.part, name);
+    if parts.is_empty() {
+        return Err(no_parts(day, args.part, name));
+    }
+
+    let text = read_input(day, args)?;
+    if args.format != Format::Human {
+        let runs = run_parallel(&parts, |_| Ok(text.clone()), 1);
+        return print_records(args, runs);
+    }
+    // Both parts of a day share the parser, so the input is parsed once.
+    let start = Instant::now();
+    let input = parts[0].parse(&text)?;
+    if args.time {
+        eprintln!("day {} parse: {}", day, format_duration(start.elapsed()));
+    }
+
+    for part in parts {
+        let start = Instant::now();
+        let answer = part.solve(input.as_ref());
+        let elapsed = start.elapsed();
+
+        println!("{}", answer.trim_end());
+        if args.time {
+            eprintln!(
+                "day {} part {}: {}",
+                part.day(),
+                part.part(),
+                format_duration(elapsed)
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Runs all days in parallel and prints the answers in order of day and
+/// part, each after the records its run logged.
+fn run_all(args: &RunArgs) -> Result<(), AocError> {
+    let variant = args.sample.clone().unwrap_or(Variant::Real);
+    let provider = InputProvider::from_env()?;
+    let parts = select_parts(None, args.part, args.implementation.as_deref());
+
+    let start = Instant::now();
+    let runs = run_parallel(
+        &parts,
+        |day| provider.load(day, &variant),
+        args.jobs.unwrap_or(0),
+    );
+    let elapsed = start.elapsed();
+    if args.format != Format::Human {
+        return print_records(args, runs);
+    }
+
+    let mut failed = 0;
+    for run in runs {
+        let answer = match run.answer {
+            // Days without this input are skipped.
+            Err(AocError::MissingInput { .. }) => continue,
+            Err(e) => {
+                failed += 1;
+                format!("error: {}", e)
+            }
+            Ok(answer) => answer,
+        };
+        eprint!("{}", run.output);
+        let answer = answer.trim_end();
+        if answer.contains('\n') {
+            println!("day {} part {}:\n{}", run.day, run.part, answer);
+        } else {
+            println!("day {} part {}: {}", run.day, run.part, answer);
+        }
+        if args.time {
+            eprintln!(
+                "day {} part {}: parse {}, solve {}",
+                run.day,
+                run.part,
+                format_duration(run.parse),
+                format_duration(run.solve)
+            );
+        }
+    }
+    if args.time {
+        eprintln!("total: {}", format_duration(elapsed));
+    }
+    if failed > 0 {
+        return Err(AocError::RunFailed { failed });
+    }
+    Ok(())
+}
+
+/// Name of the input of `run` in result records.
+fn input_name(args: &RunArgs) -> String {
+    match &args.input {
+        Some(path) if path.as_os_str() == "-" => "stdin".to_string(),
+        Some(path) => path.display().to_string(),
+        None => args.sample.clone().unwrap_or(Variant::Real).to_string(),
+    }
+}
+
+/// Prints a JSON or TSV record for each run, skipping runs without input.
+fn print_records(args: &RunArgs, runs: Vec<PartRun>) -> Result<(), AocError> {
+    let answers = Answers::load(&Answers::bundled_path())?;
+    let input = input_name(args);
+    let variant = args
+        .input
+        .is_none()
+        .then(|| input.parse::<Variant>().unwrap());
+    if args.format == Format::Tsv {
+        println!("{}", TSV_HEADER);
+    }
+
+    let mut failed = 0;
+    for run in runs {
+        if let Err(AocError::MissingInput { .. }) = run.answer {
+            continue;
+        }
+        // Answers are only known for the inputs in the input directory.
+        let status = match &variant {
+            Some(variant) => run.status(&answers, variant),
+            None if run.answer.is_err() => "error",
+            None => "unknown",
+        };
+        if run.answer.is_err() {
+            failed += 1;
+        }
+        eprint!("{}", run.output);
+        match args.format {
+            Format::Tsv => println!("{}", run.to_tsv(&input, status)),
+            _ => println!("{}", run.to_json(&input, status)),
+        }
+    }
+    if failed > 0 {
+        return Err(AocError::RunFailed { failed });
+    }
+    Ok(())
+}
+
+/// Verifies both parts of `day` on one input, printing a line per part.
+/// Returns the number of failed parts.
+fn verify_input(
+    day: u8,
+    input: &Variant,
+    provider: &InputProvider,
+    answers: &mut Answers,
+    args: &VerifyArgs,
+) -> usize {
+    let parts: Vec<&dyn Part> = registry().parts().filter(|p| p.day() == day).collect();
+    let text = match provider.load(day, input) {
+        Ok(text) => text,
+        Err(AocError::MissingInput { .. })
+            if parts
+                .iter()
+                .all(|p| answers.get(day, p.part(), input).is_none()) =>
+        {
+            if *input == Variant::Real {
+                println!("day {} {}: no input", day, input);
+            }
+            return 0;
+        }
+        Err(e) => {
+            println!("day {} {}: error: {}", day, input, e);
+            return parts.len();
+        }
+    };
+    let parsed = match parts[0].parse(&text) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            println!("day {} {}: error: {}", day, input, e);
+            return parts.len();
+        }
+    };
+
+    let mut failed = 0;
+    for part in parts {
+        let answer = part.solve(parsed.as_ref());
+        let answer = answer.trim_end();
+        let prefix = format!("day {} part {} {}", day, part.part(), input);
+        match answers.check(day, part.part(), input, answer) {
+            Verdict::Pass => println!("{}: pass", prefix),
+            Verdict::Fail { expected } => {
+                failed += 1;
+                println!("{}: FAIL, expected {}, got {}", prefix, expected, answer);
+            }
+            Verdict::Missing if args.record => {
+                answers.record(day, part.part(), input, answer);
+                println!("{}: recorded {}", prefix, answer);
+            }
+            Verdict::Missing => println!("{}: missing, got {}", prefix, answer),
+        }
+        if args.cross_check {
+            let check = CrossCheck::run(registry().impls(day, part.part()), parsed.as_ref());
+            if !check.agrees() {
+                failed += 1;
+                println!("{}: implementations disagree: {}", prefix, check);
+            }
+        }
+    }
+    failed
+}
+
+fn verify(args: &VerifyArgs) -> Result<(), AocError> {
+    let path = args.answers.clone().unwrap_or_else(Answers::bundled_path);
+    let mut answers = Answers::load(&path)?;
+    let provider = InputProvider::from_env()?;
+    let days = match args.day {
+        Some(day) if !registry().days().contains(&day) => {
+            return Err(AocError::NoSolution { day, part: None })
+        }
+        Some(day) => vec![day],
+        None => registry().days(),
+    };
+
+    let mut failed = 0;
+    for day in days {
+        let mut inputs = vec![Variant::Real];
+        for input in answers.inputs(day).into_iter().chain(args.sample.clone()) {
+            if !inputs.contains(&input) {
+                inputs.push(input);
+            }
+        }
+        for input in &inputs {
+            failed += verify_input(day, input, &provider, &mut answers, args);
+        }
+    }
+
+    if args.record {
+        answers.save(&path)?;
+    }
+    if failed > 0 {
+        return Err(AocError::Verification { failed });
+    }
+    Ok(())
+}
+
+fn print_bench_table(results: &[PartBench]) {
+    println!(
+        "{:>3} {:>4} {:<8} {:<10} {:>12} {:>12} {:>12} {:>12} {:>12} {:>12}",
+        "day",
+        "part",
+        "input",
+        "impl",
+        "parse min",
+        "parse med",
+        "parse p95",
+        "solve min",
+        "solve med",
+        "solve p95"
+    );
+    for r in results {
+        println!(
+            "{:>3} {:>4} {:<8} {:<10} {:>12} {:>12} {:>12} {:>12} {:>12} {:>12}",
+            r.day,
+            r.part,
+            r.input,
+            r.implementation,
+            format_duration(r.parse.min),
+            format_duration(r.parse.median),
+            format_duration(r.parse.p95),
+            format_duration(r.solve.min),
+            format_duration(r.solve.median),
+            format_duration(r.solve.p95),
+        );
+    }
+}
+
+fn print_regressions(regressions: &[Regression]) {
+    println!(
+        "{:>3} {:>4} {:<8} {:<10} {:<5} {:>12} {:>12} {:>8}",
+        "day", "part", "input", "impl", "phase", "baseline", "current", "change"
+    );
+    for r in regressions {
+        println!(
+            "{:>3} {:>4} {:<8} {:<10} {:<5} {:>12} {:>12} {:>7.1}%",
+            r.day,
+            r.part,
+            r.input,
+            r.implementation,
+            r.phase,
+            format_duration(r.baseline),
+            format_duration(r.current),
+            r.change()
+        );
+    }
+}
+
+fn run_bench(args: &BenchArgs) -> Result<(), AocError> {
+    let options = BenchOptions {
+        warmup: args.warmup,
+        runs: args.runs as usize,
+    };
+    let variant = args.sample.clone().unwrap_or(Variant::Real);
+    let provider = InputProvider::from_env()?;
+    let name = args.implementation.as_deref();
+    let parts = select_parts(args.day, args.part, name);
+    if let (Some(day), true) = (args.day, parts.is_empty()) {
+        return Err(no_parts(day, args.part, name));
+    }
+
+    let baseline_dir = args
+        .baseline_dir
+        .clone()
+        .unwrap_or_else(aoc::bench::baseline_dir);
+    // Fail before the benchmark runs if the baseline is missing.
+    let baseline = match &args.baseline {
+        Some(name) => Some(aoc::bench::load_baseline(&baseline_dir, name)?),
+        None => None,
+    };
+
+    let mut results = Vec::new();
+    for part in parts {
+        let text = match provider.load(part.day(), &variant) {
+            Ok(text) => text,
+            // Days without this input are skipped when benchmarking all days.
+            Err(AocError::MissingInput { .. }) if args.day.is_none() => continue,
+            Err(e) => return Err(e),
+        };
+        results.push(bench(part, &variant.to_string(), &text, &options)?);
+    }
+
+    let json = aoc::bench::to_json(&options, &results);
+    match &args.json {
+        Some(path) if path.as_os_str() == "-" => println!("{:#}", json),
+        Some(path) => {
+            std::fs::write(path, format!("{:#}\n", json)).map_err(|e| AocError::Io {
+                path: path.clone(),
+                message: e.to_string(),
+            })?;
+            print_bench_table(&results);
+        }
+        None => print_bench_table(&results),
+    }
+
+    if let Some(name) = &args.save_baseline {
+        aoc::bench::save_baseline(&baseline_dir, name, &options, &results)?;
+    }
+    if let (Some(name), Some(baseline)) = (&args.baseline, baseline) {
+        let regressions = aoc::bench::compare(&baseline, &results, args.tolerance);
+        if !regressions.is_empty() {
+            println!();
+            print_regressions(&regressions);
+            return Err(AocError::Regression {
+                baseline: name.clone(),
+                regressed: regressions.len(),
+            });
+        }
+    }
+    Ok(())
+}
+
+fn list() {
+    for day in registry().days() {
+        println!("{}", day);
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    if let Some(filter) = &cli.log {
+        aoc::output::init(filter.clone());
+    }
+    let result = match &cli.command {
+        Command::Run(args) => run(args),
+        Command::Verify(args) => verify(args),
+        Command::Bench(args) => run_bench(args),
+        Command::List => {
+            list();
+            Ok(())
+        }
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
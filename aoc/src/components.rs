@@ -0,0 +1,192 @@
+//! Flood fill and connected components of grids: points are connected if
+//! they are both passable and neighbors.
+use super::points::*;
+use super::sparse_field::SparseBoolField;
+use super::vec_field::VecField;
+use std::collections::{HashMap, VecDeque};
+
+/// A connected set of points.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Component {
+    /// Index of the component in [Labels::components].
+    pub label: usize,
+    /// First point of the component found, in reading order for fields.
+    pub start: Point,
+    /// Number of points.
+    pub size: usize,
+    pub bounds: Rect,
+}
+
+/// Components of a [VecField].
+#[derive(Clone, Debug)]
+pub struct Labels {
+    /// Label of the component of each point, `None` if it is not passable.
+    pub labels: VecField<Option<usize>>,
+    pub components: Vec<Component>,
+}
+
+/// Components of a [SparseBoolField].
+#[derive(Clone, Debug)]
+pub struct SparseLabels {
+    /// Label of the component of each set point.
+    pub labels: HashMap<Point, usize>,
+    pub components: Vec<Component>,
+}
+
+/// Breadth first search from `start` over the points that `claim` returns
+/// true for, which it does once per point.
+fn fill<I: Iterator<Item = Point>>(
+    start: Point,
+    label: usize,
+    mut claim: impl FnMut(Point) -> bool,
+    neighbors: impl Fn(Point) -> I,
+) -> Component {
+    let mut component = Component {
+        label,
+        start,
+        size: 0,
+        bounds: Rect::new(start, start),
+    };
+    let mut queue = VecDeque::new();
+    if claim(start) {
+        queue.push_back(start);
+    }
+    while let Some(current) = queue.pop_front() {
+        component.size += 1;
+        component.bounds = component.bounds.union(&Rect::new(current, current));
+        for next in neighbors(current) {
+            if claim(next) {
+                queue.push_back(next);
+            }
+        }
+    }
+    component
+}
+
+/// Points reachable from `start` through passable points, `start` included
+/// if it is passable.
+pub fn flood_fill<T>(
+    field: &VecField<T>,
+    start: Point,
+    connectivity: Connectivity,
+    passable: impl Fn(&T) -> bool,
+) -> SparseBoolField {
+    let mut result = SparseBoolField::new();
+    fill(
+        start,
+        0,
+        |p| field.get(&p).is_some_and(&passable) && result.set(&p),
+        |p| field.neighbors(p, connectivity),
+    );
+    result
+}
+
+/// Labels the connected components of passable points of `field`.
+pub fn label<T>(
+    field: &VecField<T>,
+    connectivity: Connectivity,
+    passable: impl Fn(&T) -> bool,
+) -> Labels {
+    let mut labels: VecField<Option<usize>> = field.map(|_| None);
+    let mut components = Vec::new();
+    for (point, value) in field.iter() {
+        if labels[point].is_some() || !passable(value) {
+            continue;
+        }
+        let label = components.len();
+        components.push(fill(
+            point,
+            label,
+            |p| {
+                let claimed = labels[p].is_none() && passable(&field[p]);
+                if claimed {
+                    labels[p] = Some(label);
+                }
+                claimed
+            },
+            |p| field.neighbors(p, connectivity),
+        ));
+    }
+    Labels { labels, components }
+}
+
+/// Labels the connected components of the set points of `field`.
+pub fn label_sparse(field: &SparseBoolField, connectivity: Connectivity) -> SparseLabels {
+    let mut labels = HashMap::new();
+    let mut components = Vec::new();
+    let mut points: Vec<Point> = field.iter().collect();
+    // Hash set order varies, sort to number components in reading order.
+    points.sort_by_key(|p| (p.y, p.x));
+    for point in points {
+        if labels.contains_key(&point) {
+            continue;
+        }
+        let label = components.len();
+        components.push(fill(
+            point,
+            label,
+            |p| field.get(&p) && labels.insert(p, label).is_none(),
+            |p| p.neighbors(connectivity, Topology::Unbounded),
+        ));
+    }
+    SparseLabels { labels, components }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAP: &str = "\
+##..#
+#..#.
+..#..
+.##.#
+";
+
+    fn field() -> VecField<bool> {
+        VecField::parse(MAP, "'#' or '.'", |ch| Some(ch == '#')).unwrap()
+    }
+
+    #[test]
+    fn label_test() {
+        let labels = label(&field(), Connectivity::Orthogonal, |v| *v);
+        let sizes: Vec<usize> = labels.components.iter().map(|c| c.size).collect();
+        assert_eq!(sizes, vec![3, 1, 1, 3, 1]);
+        assert_eq!(labels.labels[Point::new(1, 3)], Some(3));
+        assert_eq!(labels.labels[Point::new(0, 2)], None);
+        assert_eq!(
+            labels.components[3].bounds,
+            Rect::new(Point::new(1, 2), Point::new(2, 3))
+        );
+
+        let diagonal = label(&field(), Connectivity::All, |v| *v);
+        let sizes: Vec<usize> = diagonal.components.iter().map(|c| c.size).collect();
+        assert_eq!(sizes, vec![3, 5, 1]);
+
+        let empty = label(&field(), Connectivity::All, |v| !*v);
+        assert_eq!(empty.components.len(), 1);
+        assert_eq!(empty.components[0].start, Point::new(2, 0));
+        assert_eq!(empty.components[0].size, 11);
+    }
+
+    #[test]
+    fn flood_fill_test() {
+        let filled = flood_fill(&field(), Point::new(2, 0), Connectivity::Orthogonal, |v| {
+            !*v
+        });
+        assert_eq!(filled.len(), 7);
+        assert!(filled.get(&Point::new(0, 3)));
+        assert!(!filled.get(&Point::new(3, 3)));
+        assert!(flood_fill(&field(), Point::new(0, 0), Connectivity::All, |v| !*v).is_empty());
+    }
+
+    #[test]
+    fn label_sparse_test() {
+        let field = SparseBoolField::from_map(MAP, '#');
+        let labels = label_sparse(&field, Connectivity::Orthogonal);
+        let sizes: Vec<usize> = labels.components.iter().map(|c| c.size).collect();
+        assert_eq!(sizes, vec![3, 1, 1, 3, 1]);
+        assert_eq!(labels.labels[&Point::new(4, 3)], 4);
+        assert_eq!(labels.labels.len(), field.len());
+    }
+}
@@ -0,0 +1,61 @@
+use super::error::AocError;
+use super::parse::parse_line_separated;
+use super::solution::Solution;
+
+pub fn part1(input: &[i64]) -> i64 {
+    input
+        .windows(2)
+        .map(|w| if w[1] > w[0] { 1 } else { 0 })
+        .sum()
+}
+
+pub fn part2(input: &[i64]) -> i64 {
+    let sums = input
+        .windows(3)
+        .map(|w| w[0] + w[1] + w[2])
+        .collect::<Vec<i64>>();
+    part1(&sums)
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+    type Input = Vec<i64>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(text: &str) -> Result<Vec<i64>, AocError> {
+        parse_line_separated(text)
+    }
+
+    fn part1(depths: &Vec<i64>) -> i64 {
+        part1(depths)
+    }
+
+    fn part2(depths: &Vec<i64>) -> i64 {
+        part2(depths)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::bundled;
+
+    fn input() -> Vec<i64> {
+        Day1::parse(&bundled(1, "real")).unwrap()
+    }
+
+    #[test]
+    fn part1() {
+        let answer = super::part1(&input());
+        println!("Answer: {}", answer);
+    }
+
+    #[test]
+    fn part2() {
+        let answer = super::part2(&input());
+        println!("Answer: {}", answer);
+    }
+}
@@ -1,3 +1,5 @@
+use super::error::AocError;
+use super::solution::Solution;
 use std::collections::HashMap;
 use std::collections::VecDeque;
 
@@ -6,10 +8,7 @@ lazy_static! {
         vec!(('(', ')'), ('[', ']'), ('{', '}'), ('<', '>'),)
             .into_iter()
             .collect();
-    static ref REVERSE: HashMap<char, char> = BRACKETS
-        .iter()
-        .map(|(k, v)| (v.clone(), k.clone()))
-        .collect();
+    static ref REVERSE: HashMap<char, char> = BRACKETS.iter().map(|(k, v)| (*v, *k)).collect();
     static ref CHECK_SCORES: HashMap<char, u32> =
         vec!((')', 3), (']', 57), ('}', 1197), ('>', 25137),)
             .into_iter()
@@ -75,7 +74,7 @@ fn process_line(line: &str) -> LineResult {
     while !stack.is_empty() {
         completion.push(get_close(stack.pop_front().unwrap()));
     }
-    return LineResult::Incomplete { completion };
+    LineResult::Incomplete { completion }
 }
 
 fn check_score(line: &str) -> u32 {
@@ -105,26 +104,69 @@ pub fn part2(text: &str) -> u64 {
         .filter(|s| *s > 0)
         .collect::<Vec<u64>>();
     scores.sort();
-    return scores[scores.len() / 2];
+    // Without incomplete lines there is no middle score.
+    scores.get(scores.len() / 2).copied().unwrap_or(0)
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    type Input = String;
+    type Answer1 = u32;
+    type Answer2 = u64;
+
+    fn parse(text: &str) -> Result<String, AocError> {
+        if let Some((i, ch)) = text
+            .char_indices()
+            .find(|(_, ch)| !"()[]{}<>\n".contains(*ch))
+        {
+            return Err(AocError::parse(
+                text,
+                &text[i..i + ch.len_utf8()],
+                "expected bracket",
+            ));
+        }
+        Ok(text.to_string())
+    }
+
+    fn part1(text: &String) -> u32 {
+        part1(text)
+    }
+
+    fn part2(text: &String) -> u64 {
+        part2(text)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    fn sample_input() -> &'static str {
-        include_str!("../resources/day10_sample.txt")
+    use crate::input::bundled;
+    fn sample_input() -> String {
+        bundled(10, "sample")
     }
 
-    fn input() -> &'static str {
-        include_str!("../resources/day10.txt")
+    fn input() -> String {
+        bundled(10, "real")
     }
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn brackets_test() {
         assert_eq!(is_open('a'), false);
         assert_eq!(is_close('a'), false);
         assert_eq!(is_close('>'), true);
     }
 
+    #[test]
+    fn parse_test() {
+        assert_eq!(
+            Day10::parse("()\n[(x").unwrap_err().to_string(),
+            "line 2 col 3: expected bracket, found \"x\""
+        );
+        assert_eq!(super::part2(&Day10::parse("()\n[(]").unwrap()), 0);
+    }
+
     #[test]
     fn line_score_test() {
         assert_eq!(check_score("{([(<{}[<>[]}>{[]{[(<()>"), 1197);
@@ -132,8 +174,8 @@ mod tests {
 
     #[test]
     fn part1() {
-        assert_eq!(super::part1(sample_input()), 26397);
-        println!("part1: {}", super::part1(input()));
+        assert_eq!(super::part1(&sample_input()), 26397);
+        println!("part1: {}", super::part1(&input()));
     }
 
     #[test]
@@ -144,7 +186,7 @@ mod tests {
                 completion: vec!('}', '}', ']', ']', ')', '}', ')', ']')
             }
         );
-        assert_eq!(super::part2(sample_input()), 288957);
-        println!("part2: {}", super::part2(input()));
+        assert_eq!(super::part2(&sample_input()), 288957);
+        println!("part2: {}", super::part2(&input()));
     }
 }
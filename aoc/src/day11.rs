@@ -1,111 +1,67 @@
-use super::points::*;
-use arrayvec::ArrayVec;
-use std::collections::HashSet;
-use std::collections::VecDeque;
-use std::str::FromStr;
-
-#[derive(Clone, Copy)]
-pub struct Octopi {
-    values: [u8; 100],
-}
+use super::automaton::{Cascade, CascadeRule};
+use super::error::AocError;
+use super::points::Connectivity;
+use super::solution::Solution;
+use super::vec_field::{parse_matrix, VecField};
 
-impl ToString for Octopi {
-    fn to_string(&self) -> String {
-        let mut result = String::new();
-        for row in 0..10 {
-            for col in 0..10 {
-                result.push(('0' as u8 + self.values[row * 10 + col]) as char);
-            }
-            result.push('\n');
-        }
-        result
-    }
-}
-impl Octopi {
-    fn get(&self, point: Point) -> u8 {
-        self.values[(point.y * 10 + point.x) as usize]
-    }
+/// Energy levels of a 10x10 grid of octopi.
+pub type Octopi = VecField<u8>;
 
-    fn set(&mut self, point: Point, val: u8) {
-        self.values[(point.y * 10 + point.x) as usize] = val;
-    }
-}
+/// Octopi gain energy each step and flash above 9, which gives energy to
+/// their neighbors.
+struct Flash;
 
-fn evolve_times(o: &Octopi, times: u32) -> (u32, Octopi) {
-    (0..times).fold((0, *o), |(total, result), _| {
-        let (inc, new_o) = evolve(&result);
-        return (total + inc as u32, new_o);
-    })
-}
-fn evolve(o: &Octopi) -> (u8, Octopi) {
-    let mut result = *o;
-    let mut overflown: HashSet<Point> = HashSet::new();
-    let mut queue: VecDeque<Point> = VecDeque::new();
-    for y in 0..10 {
-        for x in 0..10 {
-            let point = Point { x, y };
-            let val = o.get(point);
-            if val == 9 {
-                overflown.insert(point);
-                queue.push_back(point);
-                result.set(point, 0);
-            } else {
-                result.set(point, val + 1);
-            }
-        }
+impl CascadeRule<u8> for Flash {
+    fn charge(&self, energy: &mut u8) -> bool {
+        *energy += 1;
+        *energy > 9
     }
 
-    while !queue.is_empty() {
-        let next = queue.pop_front().unwrap();
-        for neighbor in neighbors(&next, true, 10, 10) {
-            if overflown.contains(&neighbor) {
-                continue;
-            }
-            let val = result.get(neighbor);
-            if val == 9 {
-                overflown.insert(neighbor);
-                queue.push_back(neighbor);
-                result.set(neighbor, 0);
-            } else if !overflown.contains(&neighbor) {
-                result.set(neighbor, val + 1);
-            }
-        }
+    fn reset(&self, energy: &mut u8) {
+        *energy = 0;
     }
-    return (overflown.len() as u8, result);
 }
 
-impl FromStr for Octopi {
-    type Err = String;
-    fn from_str(text: &str) -> Result<Octopi, String> {
-        Ok(Octopi {
-            values: text
-                .lines()
-                .flat_map(|l| l.chars())
-                .map(|ch| ch as u8 - ('0' as u8))
-                .collect::<ArrayVec<u8, 100>>()
-                .into_inner()
-                .unwrap(),
-        })
+fn flashes(o: &Octopi) -> Cascade<u8, Flash> {
+    Cascade::new(o.clone(), Connectivity::All, Flash)
+}
+
+fn parse_octopi(text: &str) -> Result<Octopi, AocError> {
+    let octopi = parse_matrix(text)?;
+    if octopi.width != 10 || octopi.height != 10 {
+        return Err(AocError::parse(text, &text[..0], "expected 10x10 grid"));
     }
+    Ok(octopi)
 }
 
 pub fn part1(o: &Octopi) -> u32 {
-    let (c, _) = evolve_times(&o, 100);
-    return c;
+    flashes(o).take(100).sum::<usize>() as u32
 }
 
 pub fn part2(o: &Octopi) -> u32 {
-    let mut current = *o;
-    let mut step = 0;
-    loop {
-        step += 1;
-        let (count, new_current) = evolve(&current);
-        current = new_current;
-        if count == 100 {
-            return step;
-        }
+    let all = o.values.len();
+    flashes(o).position(|count| count == all).unwrap() as u32 + 1
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    type Input = Octopi;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(text: &str) -> Result<Octopi, AocError> {
+        parse_octopi(text)
+    }
+
+    fn part1(o: &Octopi) -> u32 {
+        part1(o)
+    }
+
+    fn part2(o: &Octopi) -> u32 {
+        part2(o)
     }
-    
 }
 
 #[cfg(test)]
@@ -113,7 +69,8 @@ mod tests {
     use super::*;
 
     fn sample_input() -> Octopi {
-        "5483143223
+        parse_octopi(
+            "5483143223
 2745854711
 5264556173
 6141336146
@@ -122,13 +79,14 @@ mod tests {
 2176841721
 6882881134
 4846848554
-5283751526"
-            .parse::<Octopi>()
-            .unwrap()
+5283751526",
+        )
+        .unwrap()
     }
 
     fn input() -> Octopi {
-        "7222221271
+        parse_octopi(
+            "7222221271
 6463754232
 3373484684
 4674461265
@@ -137,9 +95,9 @@ mod tests {
 8211411846
 4657828333
 5286325337
-5771324832"
-            .parse::<Octopi>()
-            .unwrap()
+5771324832",
+        )
+        .unwrap()
     }
 
     #[test]
@@ -153,5 +111,4 @@ mod tests {
         assert_eq!(super::part2(&sample_input()), 195);
         println!("part2: {}", super::part2(&input()));
     }
-
 }
@@ -1,17 +1,24 @@
+use super::error::AocError;
+use super::parse::split_once;
+use super::solution::Solution;
+use log::debug;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::collections::VecDeque;
 
-#[derive(Hash, Eq, PartialEq, Clone, Copy, Debug)]
+#[derive(Hash, Eq, PartialEq, Clone, Debug)]
 pub struct Cave {
-    name: &'static str,
+    name: String,
     is_small: bool,
 }
 
 impl Cave {
-    fn new(name: &'static str) -> Self {
+    fn new(name: &str) -> Self {
         let is_small = name.chars().all(|ch| ch.is_lowercase());
-        Cave { name, is_small }
+        Cave {
+            name: name.to_string(),
+            is_small,
+        }
     }
 }
 
@@ -37,13 +44,11 @@ impl CavePath {
     where
         I: IntoIterator<Item = Cave>,
     {
-        caves.into_iter().fold(
-            Some(CavePath::new(allow_double_small_cave)),
-            |result, cave| match result {
-                Some(path) => path.extend(&cave),
-                None => None,
-            },
-        )
+        caves
+            .into_iter()
+            .try_fold(CavePath::new(allow_double_small_cave), |path, cave| {
+                path.extend(&cave)
+            })
     }
 
     /// Returns a new path with a [cave] at the end if possible.
@@ -55,24 +60,24 @@ impl CavePath {
                 && cave.name != "end"
             {
                 let mut copy = self.clone();
-                copy.caves.push(*cave);
-                copy.double_small_cave = Some(*cave);
+                copy.caves.push(cave.clone());
+                copy.double_small_cave = Some(cave.clone());
                 Some(copy)
             } else {
                 None
             }
         } else {
             let mut copy = self.clone();
-            copy.caves.push(*cave);
+            copy.caves.push(cave.clone());
             if cave.is_small {
-                copy.small_caves.insert(*cave);
+                copy.small_caves.insert(cave.clone());
             }
             Some(copy)
         }
     }
-
 }
 
+#[derive(Default)]
 pub struct CaveSystem {
     caves: HashMap<Cave, Vec<Cave>>,
 }
@@ -85,24 +90,17 @@ impl CaveSystem {
     }
 
     pub fn add_path(&mut self, from: Cave, to: Cave) {
-        self.caves
-            .entry(from)
-            .and_modify(|v| v.push(to.clone()))
-            .or_insert(vec![to]);
-        self.caves
-            .entry(to)
-            .and_modify(|v| v.push(from.clone()))
-            .or_insert(vec![from]);
+        self.caves.entry(from.clone()).or_default().push(to.clone());
+        self.caves.entry(to).or_default().push(from);
     }
-    pub fn parse(text: &'static str) -> Self {
+    pub fn parse(text: &str) -> Result<Self, AocError> {
         let mut result = CaveSystem::new();
         for line in text.lines() {
-            let (from, to) = line.split_once('-').unwrap();
+            let (from, to) = split_once(text, line, "-")?;
             result.add_path(Cave::new(from), Cave::new(to));
         }
-        result
+        Ok(result)
     }
-
 }
 
 fn traverse(cave_system: &CaveSystem, allow_double_small_cave: bool) -> Vec<CavePath> {
@@ -129,7 +127,7 @@ fn traverse(cave_system: &CaveSystem, allow_double_small_cave: bool) -> Vec<Cave
         // Otherwise, find where else we can go.
         let destinations = cave_system.caves.get(last);
         if destinations.is_none() {
-            println!("No destinations from {:?}", last);
+            debug!("No destinations from {:?}", last);
             continue;
         }
         queue.extend(
@@ -150,12 +148,34 @@ pub fn part2(cave_system: &CaveSystem) -> u32 {
     traverse(cave_system, true).len() as u32
 }
 
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+    type Input = CaveSystem;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(text: &str) -> Result<CaveSystem, AocError> {
+        CaveSystem::parse(text)
+    }
+
+    fn part1(cave_system: &CaveSystem) -> u32 {
+        part1(cave_system)
+    }
+
+    fn part2(cave_system: &CaveSystem) -> u32 {
+        part2(cave_system)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::bundled;
 
     fn input() -> CaveSystem {
-        CaveSystem::parse(include_str!("../resources/day12.txt"))
+        CaveSystem::parse(&bundled(12, "real")).unwrap()
     }
     #[test]
     fn part1() {
@@ -168,9 +188,18 @@ b-d
 A-end
 b-end
 ",
-        );
+        )
+        .unwrap();
         assert_eq!(super::part1(&sample1), 10);
         println!("part1: {}", super::part1(&input()));
+
+        assert_eq!(
+            CaveSystem::parse("start-A\nstart")
+                .err()
+                .unwrap()
+                .to_string(),
+            "line 2 col 6: expected \"-\""
+        );
     }
 
     #[test]
@@ -184,7 +213,8 @@ b-d
 A-end
 b-end
 ",
-        );
+        )
+        .unwrap();
         assert_eq!(super::part2(&sample1), 36);
         println!("part2: {}", super::part2(&input()));
     }
@@ -1,4 +1,6 @@
-use super::points::*;
+use super::error::AocError;
+use super::parse::{parse_line_separated, parse_token, split_once};
+use super::solution::Solution;
 use super::sparse_field::*;
 use std::str::FromStr;
 
@@ -8,75 +10,34 @@ enum Fold {
 }
 
 impl Fold {
-    fn fold_horizontally(point: &Point, x: i32) -> Option<Point> {
-        if point.x < x {
-            // as is
-            Some(*point)
-        } else if point.x == x {
-            None
-        } else {
-            let delta = point.x - x;
-            Some(Point {
-                x: x - delta,
-                y: point.y,
-            })
-            // translated
-        }
-    }
-
-    fn fold_vertically(point: &Point, y: i32) -> Option<Point> {
-        if point.y < y {
-            // as is
-            Some(*point)
-        } else if point.y == y {
-            None
-        } else {
-            let delta = point.y - y;
-            Some(Point {
-                y: y - delta,
-                x: point.x,
-            })
-            // translated
-        }
-    }
-
-    fn fold_point(&self, point: &Point) -> Option<Point> {
-        match self {
-            Fold::Horizontal { x } => Fold::fold_horizontally(point, *x),
-            Fold::Vertical { y } => Fold::fold_vertically(point, *y),
-        }
-    }
+    /// Reflects the points beyond the fold line onto the others, the points
+    /// on the line vanish.
     fn fold(&self, field: &SparseBoolField) -> SparseBoolField {
-        let mut result = SparseBoolField::new();
-
-        for point in &field.points {
-            let folded = self.fold_point(&point);
-            if folded.is_some() {
-                result.set(&folded.unwrap());
-            }
+        match *self {
+            Fold::Horizontal { x } => field
+                .filter(|p| p.x < x)
+                .union(&field.filter(|p| p.x > x).reflect_x(x)),
+            Fold::Vertical { y } => field
+                .filter(|p| p.y < y)
+                .union(&field.filter(|p| p.y > y).reflect_y(y)),
         }
-        result
     }
 }
 impl FromStr for Fold {
-    type Err = String;
-    fn from_str(text: &str) -> Result<Fold, String> {
-        if !text.starts_with("fold along ") {
-            return Err(format!("Invalid fold instruction: {}", text));
-        }
-
-        let stripped = text.replace("fold along ", "");
+    type Err = AocError;
+    fn from_str(text: &str) -> Result<Fold, AocError> {
+        let stripped = text
+            .strip_prefix("fold along ")
+            .ok_or_else(|| AocError::parse(text, text, "expected \"fold along\""))?;
 
-        let (axis, coord_str) = stripped
-            .split_once("=")
-            .ok_or(format!("Cannot split once by ="))?;
+        let (axis, coord_str) = split_once(text, stripped, "=")?;
 
-        let coord = coord_str.parse::<i32>().map_err(|e| e.to_string())?;
+        let coord = parse_token(text, coord_str, "integer")?;
 
         match axis {
             "x" => Ok(Fold::Horizontal { x: coord }),
             "y" => Ok(Fold::Vertical { y: coord }),
-            _ => Err(format!("Wrong axis: {}", axis)),
+            _ => Err(AocError::parse(text, axis, "expected axis x or y")),
         }
     }
 }
@@ -87,44 +48,72 @@ pub struct Input {
 }
 
 impl FromStr for Input {
-    type Err = String;
+    type Err = AocError;
 
     fn from_str(text: &str) -> Result<Self, Self::Err> {
-        let (field_str, folds_str) = text
-            .split_once("\n\n")
-            .ok_or(format!("Cannot split text by \n\n"))?;
-        Ok(Input {
-            field: SparseBoolField::from_coords(field_str)?,
-            folds: folds_str
-                .lines()
-                .map(|line| line.parse::<Fold>())
-                .collect::<Result<Vec<Fold>, String>>()?,
-        })
+        let (field_str, folds_str) = split_once(text, text, "\n\n")?;
+        let field = SparseBoolField::from_coords(field_str)?;
+        let folds: Vec<Fold> =
+            parse_line_separated(folds_str).map_err(|e| e.within(text, folds_str))?;
+        if folds.is_empty() {
+            return Err(AocError::parse(
+                text,
+                &text[text.len()..],
+                "expected at least one fold",
+            ));
+        }
+        Ok(Input { field, folds })
     }
 }
 
 pub fn part1(input: &Input) -> u32 {
-    input.folds.first().unwrap().fold(&input.field).points.len() as u32
+    input
+        .folds
+        .iter()
+        .take(1)
+        .fold(input.field.clone(), |r, e| e.fold(&r))
+        .len() as u32
 }
 
 pub fn part2(input: &Input) -> SparseBoolField {
-    input.folds.iter().fold(input.field.clone(), |r,e| e.fold(&r))
+    input
+        .folds
+        .iter()
+        .fold(input.field.clone(), |r, e| e.fold(&r))
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+    type Input = Input;
+    type Answer1 = u32;
+    type Answer2 = SparseBoolField;
+
+    fn parse(text: &str) -> Result<Input, AocError> {
+        text.parse()
+    }
+
+    fn part1(input: &Input) -> u32 {
+        part1(input)
+    }
+
+    fn part2(input: &Input) -> SparseBoolField {
+        part2(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::bundled;
 
     fn sample() -> Input {
-        include_str!("../resources/day13_sample.txt")
-            .parse::<Input>()
-            .unwrap()
+        bundled(13, "sample").parse::<Input>().unwrap()
     }
 
     fn input() -> Input {
-        include_str!("../resources/day13.txt")
-            .parse::<Input>()
-            .unwrap()
+        bundled(13, "real").parse::<Input>().unwrap()
     }
     #[test]
     fn parse_test() {
@@ -132,6 +121,10 @@ mod tests {
         println!("{}", input.field);
         let result = input.folds.iter().fold(input.field, |r, e| e.fold(&r));
         println!("{}", result);
+        assert_eq!(
+            "1,1\n\n".parse::<Input>().err().unwrap().to_string(),
+            "line 3 col 1: expected at least one fold"
+        );
     }
 
     #[test]
@@ -144,5 +137,4 @@ mod tests {
     fn part2() {
         println!("{}", super::part2(&input()));
     }
-
 }
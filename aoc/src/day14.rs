@@ -1,5 +1,10 @@
+use super::error::AocError;
+use super::parse::split_once;
+use super::solution::{Implementation, Solution};
 use std::collections::HashMap;
+use std::fmt;
 use std::iter::FromIterator;
+use std::str::FromStr;
 
 pub struct Substitutions {
     table: Vec<u8>,
@@ -23,27 +28,45 @@ impl Substitutions {
         self.table[Substitutions::index(first, second)] = substitution;
     }
 
-    fn add_from_str(&mut self, text: &str) {
-        let (from_str, to_str) = text.split_once(" -> ").unwrap();
-        if from_str.len() != 2 || to_str.len() != 1 {
-            panic!("Bad text: {}", text)
+    fn add_from_str(&mut self, text: &str) -> Result<(), AocError> {
+        let (from_str, to_str) = split_once(text, text, " -> ")?;
+        let from = parse_elements(text, from_str)?;
+        let to = parse_elements(text, to_str)?;
+        match (&from[..], &to[..]) {
+            (&[first, second], &[substitution]) => {
+                self.add(first, second, substitution);
+                Ok(())
+            }
+            ([_, _], _) => Err(AocError::parse(text, to_str, "expected single element")),
+            _ => Err(AocError::parse(text, from_str, "expected pair of elements")),
         }
-        let mut from_chars = from_str.chars();
-        self.add(
-            from_chars.next().unwrap() as u8,
-            from_chars.next().unwrap() as u8,
-            to_str.chars().next().unwrap() as u8,
-        );
     }
 
-    fn from_str(text: &str) -> Self {
-        text.lines().fold(Substitutions::new(), |mut acc, line| {
-            acc.add_from_str(line);
-            acc
-        })
+    fn from_str(text: &str) -> Result<Self, AocError> {
+        let mut result = Substitutions::new();
+        for line in text.lines() {
+            result
+                .add_from_str(line)
+                .map_err(|e| e.within(text, line))?;
+        }
+        Ok(result)
     }
 }
 
+/// Parses `text`, a slice of `source`, as a list of elements, which are
+/// uppercase letters.
+fn parse_elements(source: &str, text: &str) -> Result<Vec<u8>, AocError> {
+    match text.char_indices().find(|(_, ch)| !ch.is_ascii_uppercase()) {
+        Some((i, ch)) => Err(AocError::parse(
+            source,
+            &text[i..i + ch.len_utf8()],
+            "expected element",
+        )),
+        None => Ok(text.bytes().collect()),
+    }
+}
+
+#[derive(Default)]
 pub struct Sequence {
     elements: Vec<u8>,
 }
@@ -55,12 +78,16 @@ impl Sequence {
         }
     }
 
-    fn from_str(text: &str) -> Self {
-        text.chars().map(|c| c as u8).collect()
-    }
-
-    fn to_string(&self) -> String {
-        self.elements.iter().map(|i| *i as char).collect()
+    fn from_str(text: &str) -> Result<Self, AocError> {
+        let elements = parse_elements(text, text)?;
+        if elements.len() < 2 {
+            return Err(AocError::parse(
+                text,
+                text,
+                "expected at least two elements",
+            ));
+        }
+        Ok(Sequence { elements })
     }
 
     fn substitute(&self, substitutions: &Substitutions) -> Sequence {
@@ -75,8 +102,7 @@ impl Sequence {
                 result.push(b);
             }
         }
-        let result = Sequence { elements: result };
-        return result;
+        Sequence { elements: result }
     }
 
     fn substitute_times(&self, substitutions: &Substitutions, times: u16) -> Sequence {
@@ -89,6 +115,15 @@ impl Sequence {
     }
 }
 
+impl fmt::Display for Sequence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for element in &self.elements {
+            write!(f, "{}", *element as char)?;
+        }
+        Ok(())
+    }
+}
+
 impl FromIterator<u8> for Sequence {
     fn from_iter<T: IntoIterator<Item = u8>>(iter: T) -> Self {
         Sequence {
@@ -109,18 +144,6 @@ impl FrequencySequence {
             singles: HashMap::new(),
         }
     }
-    pub fn from_str(text: &str) -> Self {
-        let chars: Vec<u8> = text.chars().map(|c| c as u8).collect();
-        let mut result = FrequencySequence::new();
-        for i in 0..chars.len() - 1 {
-            let a = chars[i];
-            let b = chars[i + 1];
-            *result.pairs.entry((a, b)).or_default() += 1;
-            *result.singles.entry(b).or_default() += 1;
-        }
-        result
-    }
-
     pub fn substitute(&self, subs: &Substitutions) -> FrequencySequence {
         let mut result = FrequencySequence::new();
         result.singles = self.singles.clone();
@@ -145,57 +168,122 @@ impl FrequencySequence {
     }
 }
 
+impl FromStr for FrequencySequence {
+    type Err = AocError;
+
+    fn from_str(text: &str) -> Result<Self, AocError> {
+        let chars = parse_elements(text, text)?;
+        let mut result = FrequencySequence::new();
+        for i in 0..chars.len().saturating_sub(1) {
+            let a = chars[i];
+            let b = chars[i + 1];
+            *result.pairs.entry((a, b)).or_default() += 1;
+            *result.singles.entry(b).or_default() += 1;
+        }
+        Ok(result)
+    }
+}
+
 pub struct Input {
     substitutions: Substitutions,
     sequence: Sequence,
 }
 
-impl Input {
-    pub fn from_str(text: &str) -> Self {
-        let (sequence, substitutions) = text.split_once("\n\n").unwrap();
-        Input {
-            sequence: Sequence::from_str(sequence),
-            substitutions: Substitutions::from_str(substitutions),
-        }
+impl FromStr for Input {
+    type Err = AocError;
+
+    fn from_str(text: &str) -> Result<Self, AocError> {
+        let (sequence, substitutions) = split_once(text, text, "\n\n")?;
+        Ok(Input {
+            sequence: Sequence::from_str(sequence)?,
+            substitutions: Substitutions::from_str(substitutions)
+                .map_err(|e| e.within(text, substitutions))?,
+        })
     }
 }
 
-pub fn part1(input: &Input) -> u32 {
+pub fn part1(input: &Input) -> u64 {
     let result_sequence = input.sequence.substitute_times(&input.substitutions, 10);
-    let mut frequency_map: HashMap<u8, u32> = HashMap::new();
+    let mut frequency_map: HashMap<u8, u64> = HashMap::new();
     for element in result_sequence.elements {
         *frequency_map.entry(element).or_default() += 1;
     }
-    
+
     let most = frequency_map.values().max().unwrap();
     let least = frequency_map.values().min().unwrap();
-    return most - least;
+    most - least
 }
 
-pub fn part2(input: &Input) -> u64 {
+/// Difference of the most and least common elements after substituting
+/// `times` times, counting pairs instead of building the sequence.
+fn frequency_difference(input: &Input, times: u16) -> u64 {
     let freq_sequence = FrequencySequence::from_str(&input.sequence.to_string())
-        .substitute_times(&input.substitutions, 40);
+        .unwrap()
+        .substitute_times(&input.substitutions, times);
 
     let most = freq_sequence.singles.values().max().unwrap();
     let least = freq_sequence.singles.values().min().unwrap();
-    return most - least;
+    most - least
+}
+
+fn part1_frequency(input: &Input) -> u64 {
+    frequency_difference(input, 10)
+}
+
+pub fn part2(input: &Input) -> u64 {
+    frequency_difference(input, 40)
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+    type Input = Input;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(text: &str) -> Result<Input, AocError> {
+        text.parse()
+    }
+
+    fn part1(input: &Input) -> u64 {
+        part1(input)
+    }
+
+    fn part2(input: &Input) -> u64 {
+        part2(input)
+    }
+
+    fn part1_impls() -> Vec<Implementation<Input, u64>> {
+        vec![("sequence", part1), ("frequency", part1_frequency)]
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::bundled;
 
     fn sample() -> Input {
-        Input::from_str(include_str!("../resources/day14_sample.txt"))
+        bundled(14, "sample").parse::<Input>().unwrap()
     }
 
     fn input() -> Input {
-        Input::from_str(include_str!("../resources/day14.txt"))
+        bundled(14, "real").parse::<Input>().unwrap()
     }
     #[test]
     fn part1() {
         println!("part1 sample: {}", super::part1(&sample()));
         println!("part1: {}", super::part1(&input()));
+        assert_eq!(part1_frequency(&input()), super::part1(&input()));
+    }
+
+    #[test]
+    fn parse_test() {
+        assert_eq!(
+            "N\n\nNN -> C".parse::<Input>().err().unwrap().to_string(),
+            "line 1 col 1: expected at least two elements, found \"N\""
+        );
     }
 
     #[test]
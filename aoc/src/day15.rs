@@ -1,4 +1,6 @@
+use super::error::AocError;
 use super::points::*;
+use super::solution::Solution;
 use super::vec_field::*;
 use std::cmp::Ordering;
 use std::collections::BinaryHeap;
@@ -26,41 +28,33 @@ impl PartialOrd for Node {
     }
 }
 
-fn expand(node: &Node, field: &VecField<u8>) -> Vec<Node> {
-    neighbors(
-        &node.position,
-        /*diagonals=*/ false,
-        field.width,
-        field.height,
-    )
-    .into_iter()
-    .map(|point| Node {
-        position: point,
-        cost: node.cost + (field.get(&point) as u32),
-    })
-    .collect()
+fn expand<'a>(node: &'a Node, field: &'a VecField<u8>) -> impl Iterator<Item = Node> + 'a {
+    field
+        .neighbors(node.position, Connectivity::Orthogonal)
+        .map(|point| Node {
+            position: point,
+            cost: node.cost + (field[point] as u32),
+        })
 }
 
-fn expand2(node: &Node, field: &RepeatedField) -> Vec<Node> {
-    neighbors(
-        &node.position,
-        /*diagonals=*/ false,
-        field.width(),
-        field.height(),
-    )
-    .into_iter()
-    .map(|point| {
-        let new_cost_from_start = node.cost + (field.get(&point) as u32);
-
-        Node {
-            position: point,
-            cost: new_cost_from_start,
-        }
-    })
-    .collect()
+fn expand2<'a>(node: &'a Node, field: &'a RepeatedField) -> impl Iterator<Item = Node> + 'a {
+    let topology = Topology::Bounded {
+        width: field.width(),
+        height: field.height(),
+    };
+    node.position
+        .neighbors(Connectivity::Orthogonal, topology)
+        .map(|point| {
+            let new_cost_from_start = node.cost + (field.get(&point) as u32);
+
+            Node {
+                position: point,
+                cost: new_cost_from_start,
+            }
+        })
 }
 
-pub fn part2(tile: VecField<u8>) -> u32 {
+pub fn part2(tile: &VecField<u8>) -> u32 {
     let field = RepeatedField::new(tile);
     let start = Point { x: 0, y: 0 };
     let finish = Point {
@@ -94,17 +88,17 @@ pub fn part2(tile: VecField<u8>) -> u32 {
             }
         }
     }
-    return 0;
+    0
 }
 
-struct RepeatedField {
-    tile: VecField<u8>,
+struct RepeatedField<'a> {
+    tile: &'a VecField<u8>,
     tile_width: u8,
     tile_height: u8,
 }
 
-impl RepeatedField {
-    fn new(tile: VecField<u8>) -> Self {
+impl<'a> RepeatedField<'a> {
+    fn new(tile: &'a VecField<u8>) -> Self {
         RepeatedField {
             tile,
             tile_width: 5,
@@ -125,10 +119,7 @@ impl RepeatedField {
         let x_in_tile = point.x % (self.tile.width as i32);
         let tile_y = point.y / (self.tile.width as i32);
         let y_in_tile = point.y % (self.tile.width as i32);
-        let mut value = self.tile.get(&Point {
-            x: x_in_tile,
-            y: y_in_tile,
-        }) as i32;
+        let mut value = self.tile[Point::new(x_in_tile, y_in_tile)] as i32;
         value += tile_x;
         value += tile_y;
 
@@ -136,7 +127,7 @@ impl RepeatedField {
             value -= 9;
         }
 
-        return value as u8;
+        value as u8
     }
 }
 
@@ -166,7 +157,7 @@ pub fn part1(field: &VecField<u8>) -> u32 {
             continue;
         }
 
-        for next in expand(&current, &field) {
+        for next in expand(&current, field) {
             if next.cost < *distances.get(&next.position).unwrap_or(&u32::MAX) {
                 distances.insert(next.position, next.cost);
                 queue.push(next);
@@ -174,19 +165,41 @@ pub fn part1(field: &VecField<u8>) -> u32 {
         }
     }
 
-    return 0;
+    0
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+    type Input = VecField<u8>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(text: &str) -> Result<VecField<u8>, AocError> {
+        parse_matrix(text)
+    }
+
+    fn part1(field: &VecField<u8>) -> u32 {
+        part1(field)
+    }
+
+    fn part2(tile: &VecField<u8>) -> u32 {
+        part2(tile)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::bundled;
 
     fn sample() -> VecField<u8> {
-        parse_matrix(include_str!("../resources/day15_sample.txt"))
+        parse_matrix(&bundled(15, "sample")).unwrap()
     }
 
     fn input() -> VecField<u8> {
-        parse_matrix(include_str!("../resources/day15.txt"))
+        parse_matrix(&bundled(15, "real")).unwrap()
     }
     #[test]
     fn part1() {
@@ -196,8 +209,8 @@ mod tests {
 
     #[test]
     fn part2() {
-        println!("part2 sample: {}", super::part2(sample()));
-        println!("part2: {}", super::part2(input()));
+        println!("part2 sample: {}", super::part2(&sample()));
+        println!("part2: {}", super::part2(&input()));
         //        println!("{}", super::part2(&input()));
     }
 }
@@ -1,85 +1,99 @@
 #![allow(dead_code)]
+use super::error::AocError;
+use super::solution::Solution;
 use std::collections::VecDeque;
 
 #[derive(Debug, Eq, PartialEq)]
-struct Packet {
+pub struct Packet {
     version: u8,
     packet_type: u8,
     payload: Payload,
 }
 
 impl Packet {
-    fn from_hex_string(text: &str) -> Self {
-        Packet::read(&mut BitQueue::from_hex_string(text))
+    fn from_hex_string(text: &str) -> Result<Self, AocError> {
+        Packet::read(&mut BitQueue::from_hex_string(text)?)
     }
-    fn read(queue: &mut BitQueue) -> Self {
-        let version = queue.read_bits_as_num(3) as u8;
-        let packet_type = queue.read_bits_as_num(3) as u8;
+    fn read(queue: &mut BitQueue) -> Result<Self, AocError> {
+        let version = queue.read_bits_as_num(3)? as u8;
+        let packet_type = queue.read_bits_as_num(3)? as u8;
         let payload = match packet_type {
-            4 => Packet::read_literal(queue),
-            _ => Packet::read_operator(queue),
+            4 => Packet::read_literal(queue)?,
+            _ => Packet::read_operator(packet_type, queue)?,
         };
 
-        Packet {
+        Ok(Packet {
             version,
             packet_type,
             payload,
-        }
+        })
     }
 
-    fn read_literal(queue: &mut BitQueue) -> Payload {
+    fn read_literal(queue: &mut BitQueue) -> Result<Payload, AocError> {
         let mut result = 0;
         loop {
             result <<= 4;
-            let next = queue.read_bits_as_num(5);
+            let next = queue.read_bits_as_num(5)?;
             if next & 0b10000 == 0 {
                 // last octet.
                 result += next;
-                return Payload::Literal { value: result };
+                return Ok(Payload::Literal { value: result });
             } else {
                 result += next & 0b01111
             }
         }
     }
 
-    fn read_operator(queue: &mut BitQueue) -> Payload {
-        let len = Packet::read_length(queue);
-        let packets = Packet::read_packets(&len, queue);
-        Payload::Operator {
+    fn read_operator(packet_type: u8, queue: &mut BitQueue) -> Result<Payload, AocError> {
+        // Subpackets that the operator [Packet::eval]s takes.
+        let (arity, expected) = match packet_type {
+            0 | 1 => (0..=usize::MAX, ""),
+            2 | 3 => (1..=usize::MAX, "expected at least one subpacket"),
+            5..=7 => (2..=2, "expected two subpackets"),
+            _ => {
+                return Err(queue.error(format!("unknown packet type {}", packet_type)));
+            }
+        };
+        let len = Packet::read_length(queue)?;
+        let packets = Packet::read_packets(&len, queue)?;
+        if !arity.contains(&packets.len()) {
+            return Err(queue.error(format!("{}, got {}", expected, packets.len())));
+        }
+        Ok(Payload::Operator {
             length: len,
             subpackets: packets,
-        }
+        })
     }
 
-    fn read_length(queue: &mut BitQueue) -> Length {
-        let len_type = queue.read_bits_as_num(1);
-        if len_type == 0 {
+    fn read_length(queue: &mut BitQueue) -> Result<Length, AocError> {
+        let len_type = queue.read_bits_as_num(1)?;
+        Ok(if len_type == 0 {
             Length::Bits {
-                value: queue.read_bits_as_num(15) as u16,
+                value: queue.read_bits_as_num(15)? as u16,
             }
         } else {
             Length::PacketCount {
-                value: queue.read_bits_as_num(11) as u16,
+                value: queue.read_bits_as_num(11)? as u16,
             }
-        }
+        })
     }
 
-    fn read_packets(len: &Length, queue: &mut BitQueue) -> Vec<Packet> {
+    fn read_packets(len: &Length, queue: &mut BitQueue) -> Result<Vec<Packet>, AocError> {
         match len {
             Length::Bits { value: bit_count } => {
                 let mut result: Vec<Packet> = Vec::new();
                 let initial_size = queue.value.len();
                 loop {
-                    result.push(Packet::read(queue));
+                    result.push(Packet::read(queue)?);
                     let read = (initial_size - queue.value.len()) as u16;
                     if read == *bit_count {
-                        return result;
+                        return Ok(result);
                     }
                     if read > *bit_count {
-                        panic!(
-                            "unaligned packages! read: {}, expected: {}",
+                        return Err(queue.error(format!(
+                            "unaligned packets, read {} bits, expected {}",
                             read, bit_count
-                        );
+                        )));
                     }
                 }
             }
@@ -96,6 +110,7 @@ impl Packet {
                 length: _,
                 subpackets,
             } => {
+                // Parsing checks the packet type and number of subpackets.
                 let args = subpackets.iter().map(|x| x.eval()).collect::<Vec<u64>>();
                 match self.packet_type {
                     0 => args.into_iter().sum(),
@@ -123,7 +138,7 @@ impl Packet {
                             0
                         }
                     }
-                    _ => panic!("Unknown packet type: {}", self.packet_type),
+                    _ => unreachable!("Unknown packet type: {}", self.packet_type),
                 }
             }
         }
@@ -149,50 +164,66 @@ enum Length {
 
 struct BitQueue {
     value: VecDeque<bool>,
+    /// Hex digits the bits were read from, for error reporting.
+    text: String,
 }
 
 impl BitQueue {
     fn new() -> Self {
         BitQueue {
             value: VecDeque::new(),
+            text: String::new(),
         }
     }
 
-    fn from_hex_string(text: &str) -> Self {
+    fn from_hex_string(text: &str) -> Result<Self, AocError> {
         let mut result = BitQueue::new();
-        for ch in text.trim().chars() {
-            result.add_char(ch);
+        let trimmed = text.trim();
+        for (i, ch) in trimmed.char_indices() {
+            result
+                .add_char(ch)
+                .map_err(|e| e.within(text, &trimmed[i..]))?;
         }
-        result
+        Ok(result)
     }
-    fn add_char(&mut self, ch: char) {
-        if !ch.is_ascii() {
-            panic!("not ascii: {}", ch);
-        }
-
+    fn add_char(&mut self, ch: char) -> Result<(), AocError> {
         let val = match ch {
-            '0'..='9' => ch as u8 - '0' as u8,
-            'A'..='F' => ch as u8 - 'A' as u8 + 10,
-            _ => panic!("Unsupported char: {}", ch),
+            '0'..='9' => ch as u8 - b'0',
+            'A'..='F' => ch as u8 - b'A' + 10,
+            _ => {
+                let text = ch.to_string();
+                return Err(AocError::parse(&text, &text, "expected hex digit"));
+            }
         };
 
+        self.text.push(ch);
         self.value.push_back(val & 0b1000 > 0);
         self.value.push_back(val & 0b0100 > 0);
         self.value.push_back(val & 0b0010 > 0);
         self.value.push_back(val & 0b0001 > 0);
+        Ok(())
     }
 
-    fn read_bits_as_num(&mut self, count: u8) -> u64 {
+    /// Error at the hex digit holding the next bit to read.
+    fn error<M: Into<String>>(&self, message: M) -> AocError {
+        let index = self.text.len() - self.value.len().div_ceil(4);
+        AocError::parse(
+            &self.text,
+            &self.text[index..(index + 1).min(self.text.len())],
+            message,
+        )
+    }
+
+    fn read_bits_as_num(&mut self, count: u8) -> Result<u64, AocError> {
         let mut result = 0;
         for _ in 0..count {
-            result = result * 2
-                + if self.value.pop_front().unwrap() {
-                    1
-                } else {
-                    0
-                }
+            let bit = self
+                .value
+                .pop_front()
+                .ok_or_else(|| self.error("unexpected end of packet"))?;
+            result = result * 2 + if bit { 1 } else { 0 }
         }
-        result
+        Ok(result)
     }
 }
 
@@ -206,32 +237,74 @@ fn total_version(p: &Packet) -> u32 {
     }
 }
 fn part1(text: &str) -> u32 {
-    total_version(&Packet::from_hex_string(text))
+    total_version(&Packet::from_hex_string(text).unwrap())
 }
 fn part2(text: &str) -> u64 {
-    Packet::from_hex_string(text).eval()
+    Packet::from_hex_string(text).unwrap().eval()
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+    type Input = Packet;
+    type Answer1 = u32;
+    type Answer2 = u64;
+
+    fn parse(text: &str) -> Result<Packet, AocError> {
+        Packet::from_hex_string(text)
+    }
+
+    fn part1(packet: &Packet) -> u32 {
+        total_version(packet)
+    }
+
+    fn part2(packet: &Packet) -> u64 {
+        packet.eval()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::bundled;
 
     #[test]
     fn simple_packet() {
-        let mut queue = BitQueue::from_hex_string("D2FE28");
-        println!("{:?}", Packet::read(&mut queue))
+        let mut queue = BitQueue::from_hex_string("D2FE28").unwrap();
+        println!("{:?}", Packet::read(&mut queue).unwrap())
     }
 
     #[test]
     fn len_type_0_packet() {
-        let mut queue = BitQueue::from_hex_string("38006F45291200");
-        println!("{:?}", Packet::read(&mut queue))
+        let mut queue = BitQueue::from_hex_string("38006F45291200").unwrap();
+        println!("{:?}", Packet::read(&mut queue).unwrap())
     }
 
     #[test]
     fn len_type_1_packet() {
-        let mut queue = BitQueue::from_hex_string("EE00D40C823060");
-        println!("{:#?}", Packet::read(&mut queue))
+        let mut queue = BitQueue::from_hex_string("EE00D40C823060").unwrap();
+        println!("{:#?}", Packet::read(&mut queue).unwrap())
+    }
+
+    #[test]
+    fn parse_error() {
+        assert_eq!(
+            Packet::from_hex_string("D2FX28").unwrap_err().to_string(),
+            "line 1 col 4: expected hex digit, found \"X\""
+        );
+        assert_eq!(
+            Packet::from_hex_string("38006F").unwrap_err().to_string(),
+            "line 1 col 7: unexpected end of packet"
+        );
+        assert_eq!(
+            Packet::from_hex_string("0A0000").unwrap_err().to_string(),
+            "line 1 col 5: expected at least one subpacket, got 0, found \"0\""
+        );
+        assert_eq!(
+            Packet::from_hex_string("1A0000").unwrap_err().to_string(),
+            "line 1 col 5: expected two subpackets, got 0, found \"0\""
+        );
     }
 
     #[test]
@@ -240,7 +313,7 @@ mod tests {
         assert_eq!(super::part1("620080001611562C8802118E34"), 12);
         assert_eq!(super::part1("C0015000016115A2E0802F182340"), 23);
         assert_eq!(super::part1("A0016C880162017C3686B18A3D4780"), 31);
-        println!("part1: {}", super::part1(include_str!("../resources/day16.txt")));
+        println!("part1: {}", super::part1(&bundled(16, "real")));
     }
 
     #[test]
@@ -253,6 +326,6 @@ mod tests {
         assert_eq!(super::part2("F600BC2D8F"), 0);
         assert_eq!(super::part2("9C005AC2F8F0"), 0);
         assert_eq!(super::part2("9C0141080250320F1802104A08"), 1);
-        println!("part2: {}", super::part2(include_str!("../resources/day16.txt")));
+        println!("part2: {}", super::part2(&bundled(16, "real")));
     }
 }
@@ -1,24 +1,42 @@
 #![allow(dead_code)]
-use crate::points::{Offset, Point, Rect};
+use crate::error::AocError;
+use crate::parse::{parse_axis_range, split_once};
+use crate::points::{Offset, Orientation, Point, Rect};
+use crate::solution::Solution;
+use log::{debug, trace};
+
+/// The probe moves in math coordinates, up is positive y.
+const Y_UP: Orientation = Orientation::YUp;
+
+fn parse_target(text: &str) -> Result<Rect, AocError> {
+    let line = text.trim();
+    let ranges = line
+        .strip_prefix("target area: ")
+        .ok_or_else(|| AocError::parse(text, line, "expected \"target area: \""))?;
+    let (x_range, y_range) = split_once(text, ranges, ", ")?;
+    let (x1, x2) = parse_axis_range(text, x_range, "x")?;
+    let (y1, y2) = parse_axis_range(text, y_range, "y")?;
+
+    Ok(Rect::new(Point::new(x1, y1), Point::new(x2, y2)))
+}
 
 fn highest_y_if_hits(target: &Rect, velocity: &Offset) -> Option<i32> {
-    let mut pos = Point::new();
+    let mut pos = Point::default();
     let mut vel = *velocity;
     let mut highest_y = pos.y;
     loop {
         if target.contains(&pos) {
             return Some(highest_y);
         }
-        pos += &vel;
+        pos += vel;
         highest_y = highest_y.max(pos.y);
 
-        vel.0 -= vel.0.signum();
-        vel.1 -= 1;
+        vel -= Offset::new(vel.x.signum(), 1);
 
         // Target is always to the right and to the bottom, so
         // if we below the target or to the right side of the target,
         // we definitely miss.
-        if pos.x > target.bottom_right.x || pos.y < target.bottom_right.y {
+        if pos.x > target.max.x || pos.y < target.bottom(Y_UP) {
             return None;
         }
     }
@@ -34,16 +52,16 @@ fn part1(target: &Rect) -> i32 {
     //
     //
     // On the other hand, if target is sufficiently large, we might want to touch its rightmost
-    let min_x = ((target.top_left.x * 2) as f64).sqrt().floor() as i32;
-    let max_x = ((target.bottom_right.x * 2) as f64).sqrt().ceil() as i32;
+    let min_x = ((target.min.x * 2) as f64).sqrt().floor() as i32;
+    let max_x = ((target.max.x * 2) as f64).sqrt().ceil() as i32;
 
-    let min_y = target.top_left.y.abs();
-    let max_y = target.bottom_right.y.abs();
+    let min_y = target.top(Y_UP).abs();
+    let max_y = target.bottom(Y_UP).abs();
     let mut highest_y = 0;
-    let mut highest_y_speed = Offset(0, 0);
+    let mut highest_y_speed = Offset::default();
     for vx in min_x..max_x {
         for vy in min_y..max_y {
-            let v = Offset(vx, vy);
+            let v = Offset::new(vx, vy);
             let y = highest_y_if_hits(target, &v).unwrap_or(i32::MIN);
             if y > highest_y {
                 highest_y = y;
@@ -52,8 +70,8 @@ fn part1(target: &Rect) -> i32 {
         }
     }
 
-    println!("highest y speed: {:?}", highest_y_speed);
-    return highest_y;
+    debug!("highest y speed: {:?}", highest_y_speed);
+    highest_y
 }
 
 fn part2(target: &Rect) -> i32 {
@@ -62,50 +80,52 @@ fn part2(target: &Rect) -> i32 {
     // multi-steps and a bigger one for single-steps. But come on, the
     // problem is small enough that we can wait for a few seconds.
 
-    let min_x = ((target.top_left.x * 2) as f64).sqrt().floor() as i32 - 1;
-    let max_x = target.bottom_right.x;
+    let min_x = ((target.min.x * 2) as f64).sqrt().floor() as i32 - 1;
+    let max_x = target.max.x;
 
-    let min_y = target.bottom_right.y - 1;
-    let max_y = target.bottom_right.y.abs() + 1;
+    let min_y = target.bottom(Y_UP) - 1;
+    let max_y = target.bottom(Y_UP).abs() + 1;
     let mut hit_count = 0;
     for vx in min_x..=max_x {
         for vy in min_y..=max_y {
-            let v = Offset(vx, vy);
+            let v = Offset::new(vx, vy);
             let y = highest_y_if_hits(target, &v);
             if y.is_some() {
-                println!("hit: {:?}", v);
+                trace!("hit: {:?}", v);
                 hit_count += 1;
             }
         }
     }
 
-    return hit_count;
+    hit_count
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+    type Input = Rect;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(text: &str) -> Result<Rect, AocError> {
+        parse_target(text)
+    }
+
+    fn part1(target: &Rect) -> i32 {
+        part1(target)
+    }
+
+    fn part2(target: &Rect) -> i32 {
+        part2(target)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use regex::Regex;
     fn parse_input(text: &str) -> Rect {
-        let re = Regex::new(r"target area: x=(-?\d+)..(-?\d+), y=(-?\d+)..(-?\d+)").unwrap();
-        let coords: Vec<i32> = re
-            .captures(text)
-            .unwrap()
-            .iter()
-            .skip(1)
-            .map(|v| v.unwrap().as_str().parse::<i32>().unwrap())
-            .collect();
-
-        return Rect {
-            top_left: Point {
-                x: coords[0],
-                y: coords[3],
-            },
-            bottom_right: Point {
-                x: coords[1],
-                y: coords[2],
-            },
-        };
+        parse_target(text).unwrap()
     }
 
     fn input() -> Rect {
@@ -117,15 +137,18 @@ mod tests {
     #[test]
     fn parse_test() {
         println!("{:?}", sample());
+        assert_eq!(
+            parse_target("target area: x=20..30, y=-10..q")
+                .unwrap_err()
+                .to_string(),
+            "line 1 col 31: expected integer, found \"q\""
+        );
     }
 
     #[test]
     fn part1() {
-        assert_eq!(sample().contains(&Point { x: 28, y: -7 }), true);
-        assert_eq!(
-            highest_y_if_hits(&sample(), &Offset(7, 2)),
-            Some(3)
-        );
+        assert!(sample().contains(&Point { x: 28, y: -7 }));
+        assert_eq!(highest_y_if_hits(&sample(), &Offset::new(7, 2)), Some(3));
         println!("part1 sample: {}", super::part1(&sample()));
         println!("part1: {}", super::part1(&input()));
     }
@@ -1,11 +1,14 @@
 #![allow(dead_code)]
+use super::error::AocError;
+use super::parse::parse_token;
+use super::solution::Solution;
+use std::fmt;
 use std::iter::Peekable;
 use std::ops::Add;
 use std::str::CharIndices;
-use std::string::ToString;
 
 #[derive(Clone, Eq, PartialEq, Debug)]
-struct PairNum {
+pub struct PairNum {
     left: Box<Elem>,
     right: Box<Elem>,
 }
@@ -22,9 +25,7 @@ impl PairNum {
     }
 
     fn reduce(&mut self) {
-        if self.try_explode() {
-            self.reduce();
-        } else if self.try_split() {
+        if self.try_explode() || self.try_split() {
             self.reduce();
         }
     }
@@ -60,7 +61,7 @@ impl PairNum {
             }
             return true;
         }
-        return false;
+        false
     }
 
     // Splits if applicable and returns true.
@@ -72,64 +73,80 @@ impl PairNum {
             *self.right = new_right;
             return true;
         }
-        return false;
+        false
     }
 
-    fn parse(text: &str) -> Self {
+    fn parse(text: &str) -> Result<Self, AocError> {
         let mut chars = text.char_indices().peekable();
-        PairNum::consume_pair(&mut chars)
+        let result = PairNum::consume_pair(text, &mut chars)?;
+        match chars.next() {
+            Some((idx, _)) => Err(PairNum::error(text, idx, "expected end of line")),
+            None => Ok(result),
+        }
     }
 
-    fn consume_pair(chars: &mut Peekable<CharIndices>) -> Self {
-        PairNum::consume('[', chars);
-        let left = PairNum::consume_element(chars);
-        PairNum::consume(',', chars);
-        let right = PairNum::consume_element(chars);
-        PairNum::consume(']', chars);
+    /// Error at the char at `idx` in `text`, or at its end.
+    fn error(text: &str, idx: usize, message: &str) -> AocError {
+        let end = text[idx..]
+            .chars()
+            .next()
+            .map_or(idx, |ch| idx + ch.len_utf8());
+        AocError::parse(text, &text[idx..end], message)
+    }
 
-        PairNum::new(left, right)
+    fn consume_pair(text: &str, chars: &mut Peekable<CharIndices>) -> Result<Self, AocError> {
+        PairNum::consume(text, '[', chars)?;
+        let left = PairNum::consume_element(text, chars)?;
+        PairNum::consume(text, ',', chars)?;
+        let right = PairNum::consume_element(text, chars)?;
+        PairNum::consume(text, ']', chars)?;
+
+        Ok(PairNum::new(left, right))
     }
 
-    fn consume_element(chars: &mut Peekable<CharIndices>) -> Elem {
-        let (_, next) = chars.peek().unwrap();
-        if *next == '[' {
-            Elem::Pair(PairNum::consume_pair(chars))
-        } else {
-            Elem::Number(PairNum::consume_num(chars))
+    fn consume_element(text: &str, chars: &mut Peekable<CharIndices>) -> Result<Elem, AocError> {
+        match chars.peek() {
+            Some((_, '[')) => Ok(Elem::Pair(PairNum::consume_pair(text, chars)?)),
+            _ => Ok(Elem::Number(PairNum::consume_num(text, chars)?)),
         }
     }
 
-    fn consume(expected: char, chars: &mut Peekable<CharIndices>) {
-        let (idx, actual) = chars.next().unwrap();
-        if actual != expected {
-            panic!(
-                "Expected '{}' but got '{} at position {}",
-                expected, actual, idx
-            );
+    fn consume(
+        text: &str,
+        expected: char,
+        chars: &mut Peekable<CharIndices>,
+    ) -> Result<(), AocError> {
+        match chars.next() {
+            Some((_, actual)) if actual == expected => Ok(()),
+            Some((idx, _)) => Err(PairNum::error(
+                text,
+                idx,
+                &format!("expected {:?}", expected),
+            )),
+            None => Err(PairNum::error(
+                text,
+                text.len(),
+                &format!("expected {:?}", expected),
+            )),
         }
     }
 
-    fn consume_num(chars: &mut Peekable<CharIndices>) -> u64 {
-        let mut num_str = String::new();
-        loop {
-            let (_, peek) = chars.peek().unwrap();
-            if peek.is_ascii_digit() {
-                num_str.push(chars.next().unwrap().1);
-            } else {
-                break;
-            }
+    fn consume_num(text: &str, chars: &mut Peekable<CharIndices>) -> Result<u64, AocError> {
+        let start = chars.peek().map_or(text.len(), |(idx, _)| *idx);
+        let mut end = start;
+        while let Some((idx, _)) = chars.next_if(|(_, ch)| ch.is_ascii_digit()) {
+            end = idx + 1;
         }
-        num_str.parse::<u64>().unwrap()
+        if start == end {
+            return Err(PairNum::error(text, start, "expected number"));
+        }
+        parse_token(text, &text[start..end], "number")
     }
 }
 
-impl ToString for PairNum {
-    fn to_string(&self) -> String {
-        format!(
-            "[{},{}]",
-            self.left.as_ref().to_string(),
-            self.right.as_ref().to_string()
-        )
+impl fmt::Display for PairNum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{},{}]", self.left, self.right)
     }
 }
 #[derive(Clone, Eq, PartialEq, Debug)]
@@ -172,12 +189,8 @@ impl Elem {
             Elem::Number(v) => Some(Elem::Number(v + num)),
             Elem::Pair(p) => {
                 let maybe_new_left = p.left.add_right_num(num);
-                match maybe_new_left {
-                    None => None,
-                    Some(new_left) => {
-                        Some(Elem::Pair(PairNum::new(new_left, p.right.as_ref().clone())))
-                    }
-                }
+                maybe_new_left
+                    .map(|new_left| Elem::Pair(PairNum::new(new_left, p.right.as_ref().clone())))
             }
         }
     }
@@ -187,12 +200,8 @@ impl Elem {
             Elem::Number(v) => Some(Elem::Number(v + num)),
             Elem::Pair(p) => {
                 let maybe_new_right = p.right.add_left_num(num);
-                match maybe_new_right {
-                    None => None,
-                    Some(new_right) => {
-                        Some(Elem::Pair(PairNum::new(p.left.as_ref().clone(), new_right)))
-                    }
-                }
+                maybe_new_right
+                    .map(|new_right| Elem::Pair(PairNum::new(p.left.as_ref().clone(), new_right)))
             }
         }
     }
@@ -219,14 +228,14 @@ impl Elem {
                                 };
                             }
                         }
-                        return Explode::Explosion {
+                        Explode::Explosion {
                             replacement: Elem::Pair(PairNum::new(
                                 replacement,
                                 p.right.as_ref().clone(),
                             )),
                             left_overflow,
                             right_overflow,
-                        };
+                        }
                     } else if let Explode::Explosion {
                         replacement,
                         left_overflow,
@@ -244,16 +253,16 @@ impl Elem {
                                 };
                             }
                         }
-                        return Explode::Explosion {
+                        Explode::Explosion {
                             replacement: Elem::Pair(PairNum::new(
                                 p.left.as_ref().clone(),
                                 replacement,
                             )),
                             left_overflow,
                             right_overflow,
-                        };
+                        }
                     } else {
-                        return Explode::None;
+                        Explode::None
                     }
                 } else {
                     // depth == 4
@@ -262,13 +271,13 @@ impl Elem {
                     if let (Elem::Number(left_num), Elem::Number(right_num)) =
                         (p.left.as_ref(), p.right.as_ref())
                     {
-                        return Explode::Explosion {
+                        Explode::Explosion {
                             replacement: Elem::Number(0),
                             left_overflow: Some(*left_num),
                             right_overflow: Some(*right_num),
-                        };
+                        }
                     } else {
-                        panic!("Pair contains not only nums: {}", p.to_string())
+                        panic!("Pair contains not only nums: {}", p)
                     }
                 }
             }
@@ -285,11 +294,11 @@ enum Explode {
     },
 }
 
-impl ToString for Elem {
-    fn to_string(&self) -> String {
+impl fmt::Display for Elem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::Number(v) => v.to_string(),
-            Self::Pair(v) => v.to_string(),
+            Self::Number(v) => write!(f, "{}", v),
+            Self::Pair(v) => write!(f, "{}", v),
         }
     }
 }
@@ -299,35 +308,65 @@ impl Add<&PairNum> for &PairNum {
     fn add(self, rhs: &PairNum) -> Self::Output {
         let mut result = PairNum::new(Elem::Pair(self.clone()), Elem::Pair(rhs.clone()));
         result.reduce();
-        return result;
+        result
     }
 }
 
-fn part1(text: &str) -> u64 {
-    let nums = text
+fn parse_numbers(text: &str) -> Result<Vec<PairNum>, AocError> {
+    let nums: Vec<PairNum> = text
         .lines()
-        .map(|line| PairNum::parse(line))
-        .collect::<Vec<PairNum>>();
+        .map(|line| PairNum::parse(line).map_err(|e| e.within(text, line)))
+        .collect::<Result<_, _>>()?;
+    if nums.is_empty() {
+        return Err(AocError::parse(
+            text,
+            &text[text.len()..],
+            "expected at least one number",
+        ));
+    }
+    Ok(nums)
+}
+
+fn part1(nums: &[PairNum]) -> u64 {
     let sum = nums.iter().skip(1).fold(nums[0].clone(), |acc, e| &acc + e);
-    return sum.magnitude();
+    sum.magnitude()
 }
 
-fn part2(text: &str) -> u64 {
-    let nums = text
-        .lines()
-        .map(|line| PairNum::parse(line))
-        .collect::<Vec<PairNum>>();
+fn part2(nums: &[PairNum]) -> u64 {
     let mut best_magnitude = u64::MIN;
-    for i in 0..nums.len() {
-        for j in 0..nums.len() {
-            best_magnitude = best_magnitude.max((&nums[i] + &nums[j]).magnitude());
+    for a in nums {
+        for b in nums {
+            best_magnitude = best_magnitude.max((a + b).magnitude());
         }
     }
-    return best_magnitude;
+    best_magnitude
 }
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u8 = 18;
+    type Input = Vec<PairNum>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(text: &str) -> Result<Vec<PairNum>, AocError> {
+        parse_numbers(text)
+    }
+
+    fn part1(nums: &Vec<PairNum>) -> u64 {
+        part1(nums)
+    }
+
+    fn part2(nums: &Vec<PairNum>) -> u64 {
+        part2(nums)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::bundled;
     #[test]
     fn add_test() {
         assert_eq!(
@@ -366,7 +405,7 @@ mod tests {
     #[test]
     fn parse_test() {
         assert_eq!(
-            PairNum::parse("[[1,2],[3,4]]"),
+            PairNum::parse("[[1,2],[3,4]]").unwrap(),
             PairNum::new(
                 Elem::Pair(PairNum::new(Elem::Number(1), Elem::Number(2))),
                 Elem::Pair(PairNum::new(Elem::Number(3), Elem::Number(4)))
@@ -375,14 +414,14 @@ mod tests {
     }
 
     fn check_split(num_str: &str, result: &str) {
-        let mut num = PairNum::parse(num_str);
-        assert_eq!(num.try_split(), true);
+        let mut num = PairNum::parse(num_str).unwrap();
+        assert!(num.try_split());
         assert_eq!(num.to_string(), result);
     }
 
     fn check_explode(num_str: &str, result: &str) {
-        let mut num = PairNum::parse(num_str);
-        assert_eq!(num.try_explode(), true);
+        let mut num = PairNum::parse(num_str).unwrap();
+        assert!(num.try_explode());
         assert_eq!(num.to_string(), result);
     }
 
@@ -412,32 +451,52 @@ mod tests {
     }
 
     fn check_reduce(num_str: &str, result: &str) {
-        let mut num = PairNum::parse(num_str);
+        let mut num = PairNum::parse(num_str).unwrap();
         num.reduce();
         assert_eq!(num.to_string(), result);
     }
 
     #[test]
     fn reduce_test() {
-        let sum = &PairNum::parse("[[[[4,3],4],4],[7,[[8,4],9]]]") + &PairNum::parse("[1,1]");
-        println!("sum: {}", sum.to_string());
+        let sum = &PairNum::parse("[[[[4,3],4],4],[7,[[8,4],9]]]").unwrap()
+            + &PairNum::parse("[1,1]").unwrap();
+        println!("sum: {}", sum);
     }
 
     #[test]
     fn magnitude_test() {
-        assert_eq!(PairNum::parse("[9,1]").magnitude(), 29);
+        assert_eq!(PairNum::parse("[9,1]").unwrap().magnitude(), 29);
         assert_eq!(
-            PairNum::parse("[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]").magnitude(),
+            PairNum::parse("[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]")
+                .unwrap()
+                .magnitude(),
             3488
         );
     }
 
+    #[test]
+    fn parse_error_test() {
+        assert_eq!(
+            parse_numbers("[1,2]\n[[1,2],x]").unwrap_err().to_string(),
+            "line 2 col 8: expected number, found \"x\""
+        );
+        assert_eq!(
+            parse_numbers("[1,2").unwrap_err().to_string(),
+            "line 1 col 5: expected ']'"
+        );
+        assert_eq!(
+            parse_numbers("").unwrap_err().to_string(),
+            "line 1 col 1: expected at least one number"
+        );
+    }
+
     #[test]
     fn part1() {
         println!(
             "{}",
             super::part1(
-                "[[[0,[5,8]],[[1,7],[9,6]]],[[4,[1,2]],[[1,4],2]]]
+                &parse_numbers(
+                    "[[[0,[5,8]],[[1,7],[9,6]]],[[4,[1,2]],[[1,4],2]]]
 [[[5,[2,8]],4],[5,[[9,9],0]]]
 [6,[[[6,2],[5,6]],[[7,6],[4,7]]]]
 [[[6,[0,7]],[0,9]],[4,[9,[9,0]]]]
@@ -447,11 +506,13 @@ mod tests {
 [[9,3],[[9,9],[6,[4,9]]]]
 [[2,[[7,7],7]],[[5,8],[[9,3],[0,2]]]]
 [[[[5,2],5],[8,[3,7]]],[[5,[7,5]],[4,4]]]"
+                )
+                .unwrap()
             )
         );
         println!(
             "part1: {}",
-            super::part1(include_str!("../resources/day18.txt"))
+            super::part1(&parse_numbers(&bundled(18, "real")).unwrap())
         );
     }
 
@@ -459,7 +520,7 @@ mod tests {
     fn part2() {
         println!(
             "part2: {}",
-            super::part2(include_str!("../resources/day18.txt"))
+            super::part2(&parse_numbers(&bundled(18, "real")).unwrap())
         );
     }
 }
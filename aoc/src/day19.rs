@@ -1,6 +1,8 @@
 #![allow(dead_code)]
-use nalgebra::Matrix3;
-use nalgebra::Vector3;
+use super::error::AocError;
+use super::parse::parse_token;
+use super::points::Point3;
+use super::solution::Solution;
 use std::collections::HashSet;
 
 #[derive(Debug)]
@@ -12,8 +14,8 @@ struct Transform {
 impl Transform {
     fn rotate(rotation: &Matrix) -> Self {
         Transform {
-            offset: vector![0, 0, 0],
-            rotation: rotation.clone(),
+            offset: Point::default(),
+            rotation: *rotation,
         }
     }
 }
@@ -29,41 +31,38 @@ impl Map {
             beacons: self
                 .beacons
                 .iter()
-                .map(|p| (t.rotation * p) + &t.offset)
+                .map(|p| rotate(&t.rotation, *p) + t.offset)
                 .collect(),
         }
     }
 }
 
-fn join_maps(maps: &Vec<Map>) -> (Map, Vec<Point>) {
+/// Joins all maps into the frame of the first one, also returning the
+/// scanner positions. Fails with the index of a map that overlaps none of
+/// the maps joined so far.
+fn join_maps(maps: &[Map]) -> Result<(Map, Vec<Point>), usize> {
     // for now, don't care for map order.
     let mut result = maps[0].clone();
-    let mut remaining = maps.iter().cloned().collect::<Vec<Map>>();
+    let mut remaining: Vec<(usize, &Map)> = maps.iter().enumerate().collect();
     let mut scanners: Vec<Point> = Vec::new();
     while !remaining.is_empty() {
-        let (mut index, mut transform): (usize, Option<Transform>) = (0, None);
-        for (i, cand) in remaining.iter().enumerate() {
-            if let Some(t) = find_intersection(&result, &cand) {
-                index = i;
-                scanners.push(t.offset.clone());
-                transform = Some(t);
-                break;
-            }
-        }
-        if transform.is_none() {
-            panic!("No intersections!");
-        }
-        let appendix = remaining[index].transform(&transform.unwrap());
+        let (index, transform) = remaining
+            .iter()
+            .enumerate()
+            .find_map(|(i, (_, cand))| find_intersection(&result, cand).map(|t| (i, t)))
+            .ok_or(remaining[0].0)?;
+        scanners.push(transform.offset);
+        let appendix = remaining[index].1.transform(&transform);
         result.beacons = result
             .beacons
             .into_iter()
-            .chain(appendix.beacons.into_iter())
+            .chain(appendix.beacons)
             .collect::<HashSet<Point>>()
             .into_iter()
             .collect::<Vec<Point>>();
         remaining.remove(index);
     }
-    (result, scanners)
+    Ok((result, scanners))
 }
 fn find_intersection(a: &Map, b: &Map) -> Option<Transform> {
     for rotation in ROTATIONS.iter() {
@@ -71,23 +70,25 @@ fn find_intersection(a: &Map, b: &Map) -> Option<Transform> {
         if let Some(offset) = find_offset(a, &rotated) {
             return Some(Transform {
                 offset,
-                rotation: rotation.clone(),
+                rotation: *rotation,
             });
         }
     }
-    return None;
+    None
 }
 
 fn find_offset(a: &Map, b: &Map) -> Option<Point> {
     for i in 0..a.beacons.len() {
-        let as_relative_to_i = a.beacons
+        let as_relative_to_i = a
+            .beacons
             .iter()
-            .map(|p| p - a.beacons[i])
+            .map(|p| *p - a.beacons[i])
             .collect::<HashSet<Point>>();
         for j in 0..b.beacons.len() {
-            let overlaps = b.beacons
+            let overlaps = b
+                .beacons
                 .iter()
-                .map(|p| p - b.beacons[j])
+                .map(|p| *p - b.beacons[j])
                 .map(|p| as_relative_to_i.contains(&p) as usize)
                 .sum::<usize>();
             if overlaps >= 12 {
@@ -98,11 +99,17 @@ fn find_offset(a: &Map, b: &Map) -> Option<Point> {
             }
         }
     }
-    return None;
+    None
 }
 
-type Point = Vector3<i32>;
-type Matrix = Matrix3<i32>;
+type Point = Point3<i32>;
+/// Rotation matrix, by rows.
+type Matrix = [[i32; 3]; 3];
+
+fn rotate(m: &Matrix, p: Point) -> Point {
+    let row = |r: [i32; 3]| r[0] * p.x + r[1] * p.y + r[2] * p.z;
+    Point::new(row(m[0]), row(m[1]), row(m[2]))
+}
 
 lazy_static! {
     static ref ROTATIONS: Vec<Matrix> = rotation_matrices();
@@ -138,14 +145,18 @@ fn rotation_matrix(alpha: i32, beta: i32, gamma: i32) -> Matrix {
     let sin_beta = my_sin(beta);
     let cos_gamma = my_cos(gamma);
     let sin_gamma = my_sin(gamma);
-    matrix![
-        cos_alpha * cos_beta,
-        cos_alpha * sin_beta * sin_gamma - sin_alpha * cos_gamma,
-        cos_alpha * sin_beta * cos_gamma + sin_alpha * sin_gamma;
-        sin_alpha * cos_beta,
-        sin_alpha * sin_beta * sin_gamma + cos_alpha * cos_gamma,
-        sin_alpha * sin_beta * cos_gamma - cos_alpha * sin_gamma;
-        -sin_beta, cos_beta * sin_gamma, cos_beta * cos_gamma;
+    [
+        [
+            cos_alpha * cos_beta,
+            cos_alpha * sin_beta * sin_gamma - sin_alpha * cos_gamma,
+            cos_alpha * sin_beta * cos_gamma + sin_alpha * sin_gamma,
+        ],
+        [
+            sin_alpha * cos_beta,
+            sin_alpha * sin_beta * sin_gamma + cos_alpha * cos_gamma,
+            sin_alpha * sin_beta * cos_gamma - cos_alpha * sin_gamma,
+        ],
+        [-sin_beta, cos_beta * sin_gamma, cos_beta * cos_gamma],
     ]
 }
 
@@ -158,59 +169,108 @@ fn rotation_matrices() -> Vec<Matrix> {
             }
         }
     }
-    return result.into_iter().collect();
+    result.into_iter().collect()
 }
-struct Input {
-    maps: Vec<Map>,
+/// Scanner maps joined into a single one, which is done while parsing to
+/// reject scanners that overlap none of the others.
+pub struct Input {
+    map: Map,
+    scanners: Vec<Point>,
 }
 
-fn parse_input(text: &str) -> Input {
-    let mut result = Input { maps: Vec::new() };
+fn parse_input(text: &str) -> Result<Input, AocError> {
+    let mut maps = Vec::new();
+    let mut headers = Vec::new();
 
     for part in text.split("\n\n") {
         let mut map = Map {
             beacons: Vec::new(),
         };
-        for line in part.lines().skip(1) {
-            let coords = line.split(",").collect::<Vec<&str>>();
-            map.beacons.push(vector![
-                coords[0].parse::<i32>().unwrap(),
-                coords[1].parse::<i32>().unwrap(),
-                coords[2].parse::<i32>().unwrap()
-            ]);
+        let mut lines = part.lines();
+        if let Some(header) = lines.next() {
+            if !header.starts_with("--- scanner ") {
+                return Err(AocError::parse(text, header, "expected scanner header"));
+            }
         }
-        result.maps.push(map);
+        headers.push(part.lines().next().unwrap_or(part));
+        for line in lines {
+            let coords = line.split(',').collect::<Vec<&str>>();
+            if coords.len() != 3 {
+                return Err(AocError::parse(text, line, "expected three coordinates"));
+            }
+            map.beacons.push(Point::new(
+                parse_token(text, coords[0], "integer")?,
+                parse_token(text, coords[1], "integer")?,
+                parse_token(text, coords[2], "integer")?,
+            ));
+        }
+        maps.push(map);
     }
-    result
+    let (map, scanners) = join_maps(&maps).map_err(|i| {
+        AocError::parse(text, headers[i], "expected scanner overlapping the others")
+    })?;
+    Ok(Input { map, scanners })
 }
 
-fn part2(scanners: &Vec<Point>) -> i32 {
+fn part2(scanners: &[Point]) -> i32 {
     let mut max = i32::MIN;
     for i in 0..(scanners.len() - 1) {
         for j in (i + 1)..scanners.len() {
-            let offset = scanners[i] - scanners[j];
-            let manh = offset[0].abs() + offset[1].abs() + offset[2].abs();
-            max = max.max(manh);
+            max = max.max(scanners[i].manhattan(scanners[j]));
         }
     }
     max
 }
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: u8 = 19;
+    type Input = Input;
+    type Answer1 = usize;
+    type Answer2 = i32;
+
+    fn parse(text: &str) -> Result<Input, AocError> {
+        parse_input(text)
+    }
+
+    fn part1(input: &Input) -> usize {
+        input.map.beacons.len()
+    }
+
+    fn part2(input: &Input) -> i32 {
+        part2(&input.scanners)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::bundled;
 
     fn sample() -> Input {
-        parse_input(include_str!("../resources/day19_sample.txt"))
+        parse_input(&bundled(19, "sample")).unwrap()
     }
 
     fn input() -> Input {
-        parse_input(include_str!("../resources/day19.txt"))
+        parse_input(&bundled(19, "real")).unwrap()
     }
 
     #[test]
     fn parts() {
-        let (map, scanners) = join_maps(&input().maps);
-        println!("part1: {}", map.beacons.len());
-        println!("part2: {}", super::part2(&scanners));
+        let input = input();
+        println!("part1: {}", input.map.beacons.len());
+        println!("part2: {}", super::part2(&input.scanners));
+    }
+
+    #[test]
+    fn parse_error_test() {
+        assert_eq!(
+            parse_input("--- scanner 0 ---\n0,0,0")
+                .err()
+                .unwrap()
+                .to_string(),
+            "line 1 col 1: expected scanner overlapping the others, found \"--- scanner 0 ---\""
+        );
     }
 }
@@ -0,0 +1,107 @@
+use super::error::AocError;
+use super::parse::{parse_token, split_once};
+use super::points::direction::{Direction, Turtle};
+use super::points::Point;
+use super::solution::Solution;
+
+/// A command like `forward 5`, with depth growing down.
+#[derive(Debug, Clone, Copy)]
+pub struct Command {
+    direction: Direction,
+    distance: i32,
+}
+
+fn parse_command(line: &str) -> Result<Command, AocError> {
+    let (direction, distance_str) = split_once(line, line, " ")?;
+    let distance = parse_token::<i32>(line, distance_str, "integer")?;
+    let direction = match direction {
+        "forward" => Direction::Right,
+        "down" => Direction::Down,
+        "up" => Direction::Up,
+        _ => {
+            return Err(AocError::parse(
+                line,
+                direction,
+                "expected forward, down or up",
+            ))
+        }
+    };
+    Ok(Command {
+        direction,
+        distance,
+    })
+}
+
+pub fn parse_input(text: &str) -> Result<Vec<Command>, AocError> {
+    text.lines()
+        .map(|line| parse_command(line).map_err(|e| e.within(text, line)))
+        .collect()
+}
+
+pub fn part1(input: &[Command]) -> i32 {
+    let mut submarine = Turtle::new(Point::default(), Direction::Right);
+    for command in input {
+        submarine.step(command.direction, command.distance);
+    }
+    submarine.position.x * submarine.position.y
+}
+
+/// Up and down change the aim, the slope of the heading.
+pub fn part2(input: &[Command]) -> i32 {
+    let mut submarine = Turtle::new(Point::default(), Direction::Right);
+    for command in input {
+        match command.direction {
+            Direction::Right => submarine.forward(command.distance),
+            direction => submarine.steer(direction.offset() * command.distance),
+        }
+    }
+    submarine.position.x * submarine.position.y
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+    type Input = Vec<Command>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(text: &str) -> Result<Vec<Command>, AocError> {
+        parse_input(text)
+    }
+
+    fn part1(commands: &Vec<Command>) -> i32 {
+        part1(commands)
+    }
+
+    fn part2(commands: &Vec<Command>) -> i32 {
+        part2(commands)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::bundled;
+
+    fn get_input() -> Vec<Command> {
+        parse_input(&bundled(2, "real")).unwrap()
+    }
+
+    #[test]
+    fn part1() {
+        println!("part 1 answer: {}", super::part1(&get_input()));
+    }
+    #[test]
+    fn part2() {
+        println!("part 2 answer: {}", super::part2(&get_input()));
+    }
+
+    #[test]
+    fn parse_test() {
+        assert_eq!(
+            parse_input("forward 5\nback 3").unwrap_err().to_string(),
+            "line 2 col 1: expected forward, down or up, found \"back\""
+        );
+    }
+}
@@ -1,178 +1,186 @@
 #![allow(dead_code)]
-use super::points::*;
-use super::sparse_field::*;
-use std::collections::HashMap;
+use super::automaton::{Automaton, Neighborhood};
+use super::error::AocError;
+use super::parse::split_once;
+use super::points::{Connectivity, Offset, Point, Rect};
+use super::solution::{Implementation, Solution};
+use super::sparse_field::SparseField;
+use super::vec_field::VecField;
 
 struct Dict {
     bits: Vec<bool>,
 }
 
-#[derive(Clone)]
-struct TrickyImage {
-    map: HashMap<Point, bool>,
-    background: bool,
-}
-
-impl TrickyImage {
-    fn new() -> Self {
-        TrickyImage {
-            map: HashMap::new(),
-            background: false,
-        }
-    }
-
-    fn get(&self, p: &Point) -> bool {
-        *self.map.get(p).unwrap_or(&self.background)
-    }
-
-    fn from_sparse_field(field: &SparseBoolField) -> Self {
-        let (tl, br) = field.bounds();
-        let mut res = TrickyImage::new();
-        for x in tl.x..=br.x {
-            for y in tl.y..=br.y {
-                let p = Point { x, y };
-                res.map.insert(p, field.get(&p));
-            }
-        }
-        res
-    }
-
-    fn to_sparse_field(&self) -> SparseBoolField {
-        let mut res = SparseBoolField::new();
-        for (k, v) in &self.map {
-            if *v {
-                res.set(k);
-            }
-        }
-        res
-    }
-
-    fn set_count(&self) -> u32 {
-        self.map.values().filter(|v| **v).count() as u32
-    }
-
-    pub fn bounds(&self) -> (Point, Point) {
-        self.map.keys().fold(
-            (
-                Point {
-                    x: i32::MAX,
-                    y: i32::MAX,
-                },
-                Point {
-                    x: i32::MIN,
-                    y: i32::MIN,
-                },
-            ),
-            |(top_left, bottom_right), point| {
-                (
-                    Point {
-                        x: top_left.x.min(point.x),
-                        y: top_left.y.min(point.y),
-                    },
-                    Point {
-                        x: bottom_right.x.max(point.x),
-                        y: bottom_right.y.max(point.y),
-                    },
-                )
-            },
-        )
+/// Checks that `text` only has `.` and `#` pixels.
+fn check_pixels(text: &str) -> Result<(), AocError> {
+    match text.char_indices().find(|(_, ch)| !".#\n".contains(*ch)) {
+        Some((i, ch)) => Err(AocError::parse(
+            text,
+            &text[i..i + ch.len_utf8()],
+            "expected '.' or '#'",
+        )),
+        None => Ok(()),
     }
 }
+
 impl Dict {
-    fn parse(text: &str) -> Dict {
-        Dict {
-            bits: text
-                .chars()
-                .map(|ch| match ch {
-                    '.' => false,
-                    '#' => true,
-                    _ => panic!("unsupported char: {}", ch),
-                })
-                .collect(),
-        }
+    fn parse(text: &str) -> Result<Dict, AocError> {
+        check_pixels(text)?;
+        Ok(Dict {
+            bits: text.chars().map(|ch| ch == '#').collect(),
+        })
     }
 
     fn get(&self, i: u16) -> bool {
         self.bits[i as usize]
     }
 
-    fn decode(&self, image: &TrickyImage) -> TrickyImage {
-        let new_background = if image.background {
-            self.get(511)
-        } else {
-            self.get(0)
-        };
-        let (tl, br) = image.bounds();
-        let mut res = TrickyImage::new();
-        res.background = new_background;
-        for x in tl.x - 1..=br.x + 1 {
-            for y in tl.y - 1..=br.y + 1 {
-                let p = Point { x, y };
-                res.map.insert(p, self.decode_at(image, &p));
-            }
-        }
-        res
+    /// Next value of the pixel at the center of `cells`.
+    fn decode(&self, cells: &Neighborhood<bool>) -> bool {
+        self.get(Dict::get_window(cells))
     }
 
-    fn decode_at(&self, image: &TrickyImage, p: &Point) -> bool {
-        self.get(Dict::get_window(image, p))
+    /// The 3x3 pixels around the center as a binary number.
+    fn get_window(cells: &Neighborhood<bool>) -> u16 {
+        to_index(cells.window().map(|pixel| pixel == Some(&true)))
     }
-    fn get_window(image: &TrickyImage, p: &Point) -> u16 {
-        let mut res = 0;
-        for y in p.y - 1..=p.y + 1 {
-            for x in p.x - 1..=p.x + 1 {
-                res = res << 1;
-                if image.get(&Point { x, y }) {
-                    res += 1
-                }
+
+    /// `image` enhanced once. The background, all of the image but the
+    /// stored pixels, changes too if the first pixel of the dictionary is
+    /// lit.
+    fn enhance(&self, image: &SparseField<bool>) -> SparseField<bool> {
+        let background = self.get(if *image.background() { 511 } else { 0 });
+        let mut result = SparseField::new(background);
+        let Some(bounds) = image.bounds() else {
+            return result;
+        };
+        let corner = Offset::new(1, 1);
+        for point in bounds.expand(1).points() {
+            let window = Rect::new(point - corner, point + corner).points();
+            let pixel = self.get(to_index(window.map(|p| *image.get(&p))));
+            if pixel != background {
+                result.set(&point, pixel);
             }
         }
-        res
+        result
     }
 }
 
-struct Input {
+/// `pixels` as a binary number, the first one is the highest bit.
+fn to_index(pixels: impl Iterator<Item = bool>) -> u16 {
+    pixels.fold(0, |res, pixel| res << 1 | pixel as u16)
+}
+
+pub struct Input {
     dict: Dict,
-    image: TrickyImage,
+    /// Infinite image, unlit but for the pixels of the input.
+    image: SparseField<bool>,
 }
 
 impl Input {
-    fn parse(text: &str) -> Input {
-        let (dict_str, field_str) = text.split_once("\n\n").unwrap();
-        Input {
-            dict: Dict::parse(dict_str),
-            image: TrickyImage::from_sparse_field(&SparseBoolField::from_map(field_str, '#')),
+    fn parse(text: &str) -> Result<Input, AocError> {
+        let (dict_str, field_str) = split_once(text, text, "\n\n")?;
+        if dict_str.len() != 512 {
+            return Err(AocError::parse(
+                text,
+                &dict_str[dict_str.len()..],
+                format!("expected 512 pixels, got {}", dict_str.len()),
+            ));
         }
+        check_pixels(field_str).map_err(|e| e.within(text, field_str))?;
+        let image = VecField::parse(field_str, "pixel", |ch| Some(ch == '#'))
+            .map_err(|e| e.within(text, field_str))?;
+        Ok(Input {
+            dict: Dict::parse(dict_str)?,
+            image: SparseField::from_vec_field(&image, Point::default(), false),
+        })
     }
 }
 
+/// Number of lit pixels after enhancing the image `steps` times.
+fn lit_after(input: &Input, steps: usize) -> u32 {
+    let image = (0..steps).fold(input.image.clone(), |image, _| input.dict.enhance(&image));
+    image.iter().filter(|(_, lit)| **lit).count() as u32
+}
+
+/// Like [lit_after], but enhances the image with an [Automaton].
+fn lit_after_automaton(input: &Input, steps: usize) -> u32 {
+    let (_, image) = input.image.to_vec_field();
+    let mut image = Automaton::infinite(
+        image,
+        *input.image.background(),
+        Connectivity::All,
+        |cells: &Neighborhood<bool>| input.dict.decode(cells),
+    );
+    for _ in 0..steps {
+        image.step();
+    }
+    image.field().values.iter().filter(|lit| **lit).count() as u32
+}
+
 fn part1(input: &Input) -> u32 {
-    let once = input.dict.decode(&input.image);
-    let twice = input.dict.decode(&once);
-    twice.set_count() as u32
+    lit_after(input, 2)
+}
+
+fn part1_automaton(input: &Input) -> u32 {
+    lit_after_automaton(input, 2)
 }
 
 fn part2(input: &Input) -> u32 {
-    let result = (0..50).fold(input.image.clone(), |acc, _| input.dict.decode(&acc));
-    result.set_count() as u32
+    lit_after(input, 50)
+}
+
+fn part2_automaton(input: &Input) -> u32 {
+    lit_after_automaton(input, 50)
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u8 = 20;
+    type Input = Input;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(text: &str) -> Result<Input, AocError> {
+        Input::parse(text)
+    }
+
+    fn part1(input: &Input) -> u32 {
+        part1(input)
+    }
+
+    fn part2(input: &Input) -> u32 {
+        part2(input)
+    }
+
+    fn part1_impls() -> Vec<Implementation<Input, u32>> {
+        vec![("sparse", part1), ("automaton", part1_automaton)]
+    }
+
+    fn part2_impls() -> Vec<Implementation<Input, u32>> {
+        vec![("sparse", part2), ("automaton", part2_automaton)]
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::bundled;
+    use crate::sparse_field::SparseBoolField;
 
     fn sample() -> Input {
-        Input::parse(include_str!("../resources/day20_sample.txt"))
+        Input::parse(&bundled(20, "sample")).unwrap()
     }
 
     fn input() -> Input {
-        Input::parse(include_str!("../resources/day20.txt"))
+        Input::parse(&bundled(20, "real")).unwrap()
     }
     #[test]
     fn dict_parse_test() {
-        let dict = Dict::parse("###...###...");
-        assert_eq!(dict.get(0), true);
-        assert_eq!(dict.get(5), false);
+        let dict = Dict::parse("###...###...").unwrap();
+        assert!(dict.get(0));
+        assert!(!dict.get(5));
     }
 
     #[test]
@@ -190,30 +198,36 @@ mod tests {
     #[test]
     fn input_parse_test() {
         let i = input();
-        assert_eq!(i.dict.get(30), true);
-        assert_eq!(i.image.get(&Point { x: 33, y: 2 }), false);
+        assert!(i.dict.get(30));
+        assert!(!i.image.get(&Point::new(33, 2)));
 
         let s = sample();
-        assert_eq!(s.dict.get(1), false);
-        assert_eq!(i.image.get(&Point { x: 2, y: 3 }), true);
+        assert!(!s.dict.get(1));
+        assert!(s.image.get(&Point::new(2, 3)));
+        assert!(!s.image.background());
     }
 
     #[test]
     fn get_window_test() {
         let i = sample();
-        assert_eq!(Dict::get_window(&i.image, &Point { x: 2, y: 2 }), 34);
-        assert_eq!(i.dict.decode_at(&i.image, &Point { x: 2, y: 2 }), true);
+        let (origin, image) = i.image.to_vec_field();
+        assert_eq!(origin, Point::default());
+        let cells = Neighborhood::new(&image, Some(&false), Point::new(2, 2), Connectivity::All);
+        assert_eq!(Dict::get_window(&cells), 34);
+        assert!(i.dict.decode(&cells));
     }
 
     #[test]
     fn part1() {
         assert_eq!(super::part1(&sample()), 35);
+        assert_eq!(part1_automaton(&sample()), 35);
         println!("part1: {}", super::part1(&input()));
     }
 
     #[test]
     fn part2() {
         assert_eq!(super::part2(&sample()), 3351);
+        assert_eq!(part2_automaton(&sample()), 3351);
         println!("part2: {}", super::part2(&input()));
     }
 }
@@ -1,6 +1,11 @@
 #![allow(dead_code)]
 
+use super::error::AocError;
+use super::parse::{parse_token, split_once};
+use super::solution::Solution;
+use log::trace;
 use std::collections::HashMap;
+use std::str::FromStr;
 
 struct DeterministicDice {
     _current: u64,
@@ -24,7 +29,7 @@ impl Dice for DeterministicDice {
     fn next(&mut self) -> u64 {
         self._current = (self._current + 1) % 100;
         self._roll_count += 1;
-        return self._current;
+        self._current
     }
 
     fn roll_count(&self) -> u64 {
@@ -70,7 +75,7 @@ impl DiracDice {
 
 fn part2(p1: u8, p2: u8) -> u128 {
     // Start with a one universe.
-    let mut universes = vec![(Universe::new(&vec![p1, p2]), 1)]
+    let mut universes = vec![(Universe::new(&[p1, p2]), 1)]
         .into_iter()
         .collect::<HashMap<Universe, u128>>();
 
@@ -113,7 +118,7 @@ struct Universe {
 }
 
 impl Universe {
-    fn new(positions: &Vec<u8>) -> Self {
+    fn new(positions: &[u8]) -> Self {
         Universe {
             players: positions.iter().map(|&v| Pawn::new(v)).collect(),
             dice: DiracDice {},
@@ -170,7 +175,7 @@ fn part1(p1pos: u8, p2pos: u8) -> u64 {
     let mut p2 = Pawn::new(p2pos);
     let mut dice = DeterministicDice::new();
     loop {
-        println!("p1: {}, p2: {}", p1.score, p2.score);
+        trace!("p1: {}, p2: {}", p1.score, p2.score);
         p1.turn(&mut dice);
         if p1.score >= 1000 {
             break;
@@ -186,6 +191,62 @@ fn part1(p1pos: u8, p2pos: u8) -> u64 {
     loser.score * dice.roll_count()
 }
 
+pub struct StartingPositions {
+    p1: u8,
+    p2: u8,
+}
+
+impl FromStr for StartingPositions {
+    type Err = AocError;
+
+    fn from_str(text: &str) -> Result<Self, AocError> {
+        let positions = text
+            .lines()
+            .map(|line| {
+                let (_, position) = split_once(text, line, ": ")?;
+                let position = position.trim();
+                match parse_token(text, position, "position")? {
+                    value @ 1..=10 => Ok(value),
+                    _ => Err(AocError::parse(
+                        text,
+                        position,
+                        "expected position from 1 to 10",
+                    )),
+                }
+            })
+            .collect::<Result<Vec<u8>, AocError>>()?;
+        match positions[..] {
+            [p1, p2] => Ok(StartingPositions { p1, p2 }),
+            _ => Err(AocError::parse(
+                text,
+                &text[..0],
+                format!("expected 2 players, got {}", positions.len()),
+            )),
+        }
+    }
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: u8 = 21;
+    type Input = StartingPositions;
+    type Answer1 = u64;
+    type Answer2 = u128;
+
+    fn parse(text: &str) -> Result<StartingPositions, AocError> {
+        text.parse()
+    }
+
+    fn part1(positions: &StartingPositions) -> u64 {
+        part1(positions.p1, positions.p2)
+    }
+
+    fn part2(positions: &StartingPositions) -> u128 {
+        part2(positions.p1, positions.p2)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -203,4 +264,28 @@ mod tests {
         assert_eq!(practice, 444356092776315);
         println!("part2: {}", super::part2(8, 9));
     }
+
+    #[test]
+    fn parse_test() {
+        let positions = "Player 1 starting position: 4\nPlayer 2 starting position: 8"
+            .parse::<StartingPositions>()
+            .unwrap();
+        assert_eq!((positions.p1, positions.p2), (4, 8));
+        assert_eq!(
+            "Player 1 starting position: 4\nPlayer 2 starting position: x"
+                .parse::<StartingPositions>()
+                .err()
+                .unwrap()
+                .to_string(),
+            "line 2 col 29: expected position, found \"x\""
+        );
+        assert_eq!(
+            "Player 1 starting position: 0\nPlayer 2 starting position: 250"
+                .parse::<StartingPositions>()
+                .err()
+                .unwrap()
+                .to_string(),
+            "line 1 col 29: expected position from 1 to 10, found \"0\""
+        );
+    }
 }
@@ -1,7 +1,10 @@
 #![allow(dead_code)]
 use std::ops::Range;
 
-use regex::Regex;
+use super::error::AocError;
+use super::parse::{parse_axis_range, split_once};
+use super::solution::{Implementation, Solution};
+use log::{debug, trace};
 
 #[derive(Debug, Clone)]
 struct Cuboid {
@@ -12,18 +15,24 @@ struct Cuboid {
 }
 
 impl Cuboid {
-    fn from_str(text: &str) -> Self {
-        let nums = Regex::new(r"-?\d+")
-            .unwrap()
-            .captures_iter(text)
-            .map(|c| c.get(0).unwrap().as_str().parse::<i64>().unwrap())
-            .collect::<Vec<i64>>();
-        Cuboid {
-            on: text.starts_with("on "),
-            xs: nums[0]..(nums[1] + 1),
-            ys: nums[2]..(nums[3] + 1),
-            zs: nums[4]..(nums[5] + 1),
-        }
+    fn from_str(text: &str) -> Result<Self, AocError> {
+        let (state, ranges) = split_once(text, text, " ")?;
+        let on = match state {
+            "on" => true,
+            "off" => false,
+            _ => return Err(AocError::parse(text, state, "expected on or off")),
+        };
+        let (xs, rest) = split_once(text, ranges, ",")?;
+        let (ys, zs) = split_once(text, rest, ",")?;
+        let (x1, x2) = parse_axis_range::<i64>(text, xs, "x")?;
+        let (y1, y2) = parse_axis_range::<i64>(text, ys, "y")?;
+        let (z1, z2) = parse_axis_range::<i64>(text, zs, "z")?;
+        Ok(Cuboid {
+            on,
+            xs: x1..(x2 + 1),
+            ys: y1..(y2 + 1),
+            zs: z1..(z2 + 1),
+        })
     }
 
     fn contains(&self, x: i64, y: i64, z: i64) -> bool {
@@ -38,24 +47,29 @@ struct Instruction {
 }
 
 impl Instruction {
-    fn from_str(text: &str) -> Self {
-        Instruction {
-            on: text.starts_with("on "),
-            cuboid: Cuboid::from_str(text),
-        }
+    fn from_str(text: &str) -> Result<Self, AocError> {
+        let cuboid = Cuboid::from_str(text)?;
+        Ok(Instruction {
+            on: cuboid.on,
+            cuboid,
+        })
     }
 }
 
 #[derive(Debug)]
-struct Input {
+pub struct Input {
     cuboids: Vec<Cuboid>,
 }
 
 impl Input {
-    fn from_str(text: &str) -> Self {
-        Input {
-            cuboids: text.trim().lines().map(Cuboid::from_str).collect(),
-        }
+    fn from_str(text: &str) -> Result<Self, AocError> {
+        Ok(Input {
+            cuboids: text
+                .trim()
+                .lines()
+                .map(|line| Cuboid::from_str(line).map_err(|e| e.within(text, line)))
+                .collect::<Result<Vec<Cuboid>, AocError>>()?,
+        })
     }
 }
 
@@ -79,7 +93,30 @@ fn part1(input: &Input) -> u64 {
     result
 }
 
+/// Part 1 by cutting the cuboids to the initialization area and counting
+/// their volume like [part2] does.
+fn part1_cut_out(input: &Input) -> u64 {
+    let area = -50..51;
+    let cuboids: Vec<Cuboid> = input
+        .cuboids
+        .iter()
+        .map(|c| Cuboid {
+            xs: c.xs.start.max(area.start)..c.xs.end.min(area.end),
+            ys: c.ys.start.max(area.start)..c.ys.end.min(area.end),
+            zs: c.zs.start.max(area.start)..c.zs.end.min(area.end),
+            on: c.on,
+        })
+        .filter(|c| !c.xs.is_empty() && !c.ys.is_empty() && !c.zs.is_empty())
+        .collect();
+    volume(&cuboids)
+}
+
 fn part2(input: &Input) -> u64 {
+    volume(&input.cuboids)
+}
+
+/// Number of cubes that are on after applying all of `cuboids`.
+fn volume(cuboids: &[Cuboid]) -> u64 {
     /*
 
     main idea:
@@ -95,13 +132,11 @@ fn part2(input: &Input) -> u64 {
 
      */
     let mut result: Vec<Cuboid> = Vec::new();
-    let mut i = 0;
-    for next in &input.cuboids {
-        println!("instruction: {}, result length: {}", i, result.len());
+    for (i, next) in cuboids.iter().enumerate() {
+        debug!("instruction: {}, result length: {}", i, result.len());
         if i == 3 {
-            println!("at 3: {:?}", result);
+            trace!("at 3: {:?}", result);
         }
-        i += 1;
         if result.is_empty() {
             if !next.on {
                 continue;
@@ -191,29 +226,62 @@ fn intervals(input: &Range<i64>, cut: &Range<i64>) -> Vec<(bool, Range<i64>)> {
     }
     result
 }
+pub struct Day22;
+
+impl Solution for Day22 {
+    const DAY: u8 = 22;
+    type Input = Input;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(text: &str) -> Result<Input, AocError> {
+        Input::from_str(text)
+    }
+
+    fn part1(input: &Input) -> u64 {
+        part1(input)
+    }
+
+    fn part2(input: &Input) -> u64 {
+        part2(input)
+    }
+
+    fn part1_impls() -> Vec<Implementation<Input, u64>> {
+        vec![("scan", part1), ("cut-out", part1_cut_out)]
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::bundled;
 
     fn sample() -> Input {
-        Input::from_str(include_str!("../resources/day22_sample.txt"))
+        Input::from_str(&bundled(22, "sample")).unwrap()
     }
     fn sample2() -> Input {
-        Input::from_str(include_str!("../resources/day22_sample2.txt"))
+        Input::from_str(&bundled(22, "sample2")).unwrap()
     }
 
     fn input() -> Input {
-        Input::from_str(include_str!("../resources/day22.txt"))
+        Input::from_str(&bundled(22, "real")).unwrap()
     }
     #[test]
     fn parse_test() {
-        let cuboid = Cuboid::from_str("on x=-20..26,y=-36..17,z=-47..7");
+        let cuboid = Cuboid::from_str("on x=-20..26,y=-36..17,z=-47..7").unwrap();
         println!("result: {:?}", cuboid);
+        assert_eq!(
+            Input::from_str("on x=1..2,y=1..2,z=1..2\noff x=a..2,y=1..2,z=1..2")
+                .unwrap_err()
+                .to_string(),
+            "line 2 col 7: expected integer, found \"a\""
+        );
     }
 
     #[test]
     fn part1() {
         assert_eq!(super::part1(&sample()), 590784);
+        assert_eq!(part1_cut_out(&sample()), 590784);
         println!("part1: {}", super::part1(&input()));
     }
 
@@ -0,0 +1,159 @@
+use super::error::AocError;
+use super::solution::Solution;
+use std::collections::HashSet;
+
+pub fn is_bit_set(num: usize, position: usize) -> bool {
+    num & (1 << position) != 0
+}
+
+fn count_bits(input: &[usize], position: usize) -> usize {
+    input
+        .iter()
+        .fold(0usize, |acc, i| acc + is_bit_set(*i, position) as usize)
+}
+
+fn get_most_popular_bit(input: &[usize], position: usize) -> bool {
+    let set_bits = count_bits(input, position);
+    let unset_bits = input.len() - set_bits;
+    set_bits >= unset_bits
+}
+
+pub fn part1(input: &Input) -> u32 {
+    let mut gamma = 0;
+    let mut epsilon = 0;
+    let mut mask = 1;
+    for i in 0..input.length {
+        let most_popular_bit = get_most_popular_bit(&input.data, i);
+        if most_popular_bit {
+            gamma += mask;
+        } else {
+            epsilon += mask;
+        }
+        mask <<= 1;
+    }
+    gamma * epsilon
+}
+
+pub fn part2(input: &Input) -> usize {
+    let mut oxygen_candidates = input.data.clone();
+    let mut position = input.length;
+    while oxygen_candidates.len() > 1 {
+        position -= 1;
+        let most_popular_bit = get_most_popular_bit(&oxygen_candidates, position);
+        oxygen_candidates = oxygen_candidates
+            .iter()
+            .cloned()
+            .filter(|&x| is_bit_set(x, position) == most_popular_bit)
+            .collect();
+    }
+    let oxygen = oxygen_candidates[0];
+
+    position = input.length;
+    let mut co2_candidates = input.data.clone();
+    while co2_candidates.len() > 1 {
+        position -= 1;
+        let least_popular_bit = !get_most_popular_bit(&co2_candidates, position);
+        co2_candidates = co2_candidates
+            .iter()
+            .cloned()
+            .filter(|&x| is_bit_set(x, position) == least_popular_bit)
+            .collect();
+    }
+    let co2 = co2_candidates[0];
+    co2 * oxygen
+}
+
+pub struct Input {
+    data: Vec<usize>,
+    length: usize,
+}
+
+fn parse_input(text: &str) -> Result<Input, AocError> {
+    let mut result = Input {
+        data: Vec::new(),
+        length: 0,
+    };
+    let mut seen = HashSet::new();
+    for line in text.lines() {
+        if let Some((i, ch)) = line.char_indices().find(|(_, ch)| !"01".contains(*ch)) {
+            return Err(AocError::parse(
+                text,
+                &line[i..i + ch.len_utf8()],
+                "expected bit",
+            ));
+        }
+        if result.data.is_empty() {
+            result.length = line.len();
+        } else if line.len() != result.length {
+            return Err(AocError::parse(
+                text,
+                line,
+                format!("expected {} bits", result.length),
+            ));
+        }
+        // Part 2 narrows the numbers down to one, which duplicates prevent.
+        if !seen.insert(line) {
+            return Err(AocError::parse(text, line, "expected unique binary number"));
+        }
+        result.data.push(usize::from_str_radix(line, 2)?);
+    }
+    if result.data.is_empty() {
+        return Err(AocError::parse(text, text, "expected binary numbers"));
+    }
+    Ok(result)
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+    type Input = Input;
+    type Answer1 = u32;
+    type Answer2 = usize;
+
+    fn parse(text: &str) -> Result<Input, AocError> {
+        parse_input(text)
+    }
+
+    fn part1(input: &Input) -> u32 {
+        part1(input)
+    }
+
+    fn part2(input: &Input) -> usize {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::bundled;
+
+    fn sample() -> Input {
+        parse_input(&bundled(3, "sample")).unwrap()
+    }
+
+    fn input() -> Input {
+        parse_input(&bundled(3, "real")).unwrap()
+    }
+
+    #[test]
+    fn part1() {
+        println!("part1 sample answer is {}", super::part1(&sample()));
+        println!("part1 answer is {}", super::part1(&input()));
+    }
+
+    #[test]
+    fn parse_test() {
+        assert_eq!(
+            parse_input("101\n011\n101").err().unwrap().to_string(),
+            "line 3 col 1: expected unique binary number, found \"101\""
+        );
+    }
+
+    #[test]
+    fn part2() {
+        println!("part2 sample answer is {}", super::part2(&sample()));
+        println!("part2 answer is {}", super::part2(&input()));
+    }
+}
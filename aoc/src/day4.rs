@@ -1,7 +1,11 @@
-use std::result::Result;
+use super::error::AocError;
+use super::parse::{parse_comma_separated, parse_token};
+use super::solution::Solution;
+use log::debug;
 use std::str::FromStr;
 
-struct Input {
+#[derive(Clone)]
+pub struct Input {
     numbers: Vec<usize>,
     boards: Vec<Board>,
 }
@@ -18,28 +22,29 @@ impl Input {
     }
 }
 impl FromStr for Input {
-    type Err = String;
+    type Err = AocError;
 
-    fn from_str(input: &str) -> Result<Self, String> {
+    fn from_str(input: &str) -> Result<Self, AocError> {
+        let mut parts = input.split("\n\n");
+        // `split` always yields at least one part.
+        let nums_str = parts.next().unwrap();
         let mut result = Input {
-            numbers: Vec::new(),
+            numbers: parse_comma_separated(nums_str)?,
             boards: Vec::new(),
         };
-        let mut parts = input.split("\n\n");
-        let nums_str = parts.next().ok_or(format!("Empty input"))?;
-        for num_str in nums_str.split(",") {
-            result
-                .numbers
-                .push(num_str.parse::<usize>().map_err(|e| e.to_string())?)
-        }
 
         for board_str in parts {
-            result.boards.push(board_str.parse::<Board>()?);
+            result.boards.push(
+                board_str
+                    .parse::<Board>()
+                    .map_err(|e| e.within(input, board_str))?,
+            );
         }
 
-        return Ok(result);
+        Ok(result)
     }
 }
+#[derive(Clone)]
 struct Board {
     cols: usize,
     rows: usize,
@@ -47,9 +52,9 @@ struct Board {
 }
 
 impl FromStr for Board {
-    type Err = String;
+    type Err = AocError;
 
-    fn from_str(text: &str) -> Result<Self, String> {
+    fn from_str(text: &str) -> Result<Self, AocError> {
         let mut result = Board {
             cols: 0,
             rows: 0,
@@ -57,23 +62,32 @@ impl FromStr for Board {
         };
 
         for row_str in text.lines() {
-            result.rows += 1;
-            result.cols = 0;
+            let start = result.numbers.len();
             for num_str in row_str.split_whitespace() {
-                result.cols += 1;
                 result.numbers.push(BoardNumber {
                     is_marked: false,
-                    value: num_str.parse::<usize>().map_err(|e| e.to_string())?,
+                    value: parse_token(text, num_str, "integer")?,
                 });
             }
+            let cols = result.numbers.len() - start;
+            if result.rows == 0 {
+                result.cols = cols;
+            } else if cols != result.cols {
+                return Err(AocError::parse(
+                    text,
+                    row_str,
+                    format!("expected {} numbers", result.cols),
+                ));
+            }
+            result.rows += 1;
         }
 
-        return Ok(result);
+        Ok(result)
     }
 }
 
-pub fn part1(text: &str) -> usize {
-    let mut input = text.parse::<Input>().unwrap();
+pub fn part1(input: &Input) -> usize {
+    let mut input = input.clone();
     let len = input.numbers.len();
     for i in 0..len {
         let number = input.numbers[i];
@@ -86,22 +100,22 @@ pub fn part1(text: &str) -> usize {
                 .find(|b| b.is_winner())
                 .unwrap()
                 .unmarked_sum();
-            println!("sum: {}, number: {}", sum, number);
+            debug!("sum: {}, number: {}", sum, number);
             return sum * number;
         }
     }
-    return 0;
+    0
 }
 
-pub fn part2(text: &str) -> usize {
-    let mut input = text.parse::<Input>().unwrap();
+pub fn part2(input: &Input) -> usize {
+    let mut input = input.clone();
     let len = input.numbers.len();
     let mut remaining_boards: Vec<Board> = Vec::new();
     let mut winned_boards: Vec<Board> = Vec::new();
     remaining_boards.append(&mut input.boards);
     let mut last_number = 0;
     for i in 0..len {
-        if remaining_boards.len() == 0 {
+        if remaining_boards.is_empty() {
             break;
         }
         last_number = input.numbers[i];
@@ -118,12 +132,15 @@ pub fn part2(text: &str) -> usize {
             }
         }
     }
-    return winned_boards.last().unwrap().unmarked_sum() * last_number;
+    // No board wins, like in part 1.
+    winned_boards
+        .last()
+        .map_or(0, |board| board.unmarked_sum() * last_number)
 }
 
 impl Board {
     fn mark(&mut self, num: usize) {
-        for mut number in &mut self.numbers {
+        for number in &mut self.numbers {
             if number.value == num {
                 number.is_marked = true
             }
@@ -140,7 +157,7 @@ impl Board {
                 return false;
             }
         }
-        return true;
+        true
     }
 
     fn is_col_full(&self, col: usize) -> bool {
@@ -149,7 +166,7 @@ impl Board {
                 return false;
             }
         }
-        return true;
+        true
     }
 
     fn is_winner(&self) -> bool {
@@ -164,7 +181,7 @@ impl Board {
                 return true;
             }
         }
-        return false;
+        false
     }
 
     fn unmarked_sum(&self) -> usize {
@@ -174,25 +191,74 @@ impl Board {
                 sum += num.value
             }
         }
-        return sum;
+        sum
     }
 }
 
+#[derive(Clone)]
 struct BoardNumber {
     value: usize,
     is_marked: bool,
 }
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+    type Input = Input;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(text: &str) -> Result<Input, AocError> {
+        text.parse()
+    }
+
+    fn part1(input: &Input) -> usize {
+        part1(input)
+    }
+
+    fn part2(input: &Input) -> usize {
+        part2(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use crate::input::bundled;
+
+    fn sample() -> Input {
+        bundled(4, "sample").parse().unwrap()
+    }
+
+    fn input() -> Input {
+        bundled(4, "real").parse().unwrap()
+    }
+
     #[test]
     fn part1() {
-        println!("part1 sample: {}", super::part1(include_str!("sample.txt")));
-        println!("part1: {}", super::part1(include_str!("input.txt")));
+        println!("part1 sample: {}", super::part1(&sample()));
+        println!("part1: {}", super::part1(&input()));
+    }
+
+    #[test]
+    fn parse_test() {
+        assert_eq!(
+            "2,3\n\n1\n2 3\n"
+                .parse::<Input>()
+                .err()
+                .unwrap()
+                .to_string(),
+            "line 4 col 1: expected 1 numbers, found \"2 3\""
+        );
+        let losing = "1,4\n\n1 2\n3 4".parse::<Input>().unwrap();
+        assert_eq!(super::part1(&losing), 0);
+        assert_eq!(super::part2(&losing), 0);
     }
 
     #[test]
     fn part2() {
-        println!("part2 sample: {}", super::part2(include_str!("sample.txt")));
-        println!("part2: {}", super::part2(include_str!("input.txt")));
+        println!("part2 sample: {}", super::part2(&sample()));
+        println!("part2: {}", super::part2(&input()));
     }
 }
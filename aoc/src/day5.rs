@@ -0,0 +1,104 @@
+use super::error::AocError;
+use super::parse::parse_line_separated;
+use super::points::line::{Raster, Segment};
+use super::points::Point;
+use super::solution::{Implementation, Solution};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+pub struct Input {
+    lines: Vec<Segment>,
+}
+
+impl FromStr for Input {
+    type Err = AocError;
+
+    fn from_str(txt: &str) -> Result<Self, AocError> {
+        Ok(Input {
+            lines: parse_line_separated(txt)?,
+        })
+    }
+}
+
+/// Number of points covered by at least two of `lines`.
+fn overlaps<'a>(lines: impl Iterator<Item = &'a Segment>, raster: Raster) -> usize {
+    let mut frequency: HashMap<Point, usize> = HashMap::new();
+    for line in lines {
+        for point in line.points(raster) {
+            *frequency.entry(point).or_default() += 1;
+        }
+    }
+    frequency.values().filter(|count| **count > 1).count()
+}
+
+pub fn part1(input: &Input) -> usize {
+    overlaps(
+        input
+            .lines
+            .iter()
+            .filter(|line| line.is_horizontal() || line.is_vertical()),
+        Raster::Lattice,
+    )
+}
+
+pub fn part2(input: &Input) -> usize {
+    overlaps(input.lines.iter(), Raster::Lattice)
+}
+
+/// Same as [part2] on octilinear lines, which all puzzle inputs have.
+fn part2_bresenham(input: &Input) -> usize {
+    overlaps(input.lines.iter(), Raster::Bresenham)
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+    type Input = Input;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(text: &str) -> Result<Input, AocError> {
+        text.parse()
+    }
+
+    fn part1(input: &Input) -> usize {
+        part1(input)
+    }
+
+    fn part2(input: &Input) -> usize {
+        part2(input)
+    }
+
+    fn part2_impls() -> Vec<Implementation<Input, usize>> {
+        vec![("lattice", Self::part2), ("bresenham", part2_bresenham)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::bundled;
+
+    #[test]
+    fn it_works() {
+        let sample = bundled(5, "sample").parse::<Input>().unwrap();
+        let input = bundled(5, "real").parse::<Input>().unwrap();
+        println!("Part1 sample: {}", super::part1(&sample));
+        println!("Part1 : {}", super::part1(&input));
+        println!("Part2 sample: {}", super::part2(&sample));
+        println!("Part2 : {}", super::part2(&input));
+    }
+
+    #[test]
+    fn any_slope_test() {
+        let input = "0,0 -> 6,3\n6,0 -> 0,3\n2,1 -> 2,5"
+            .parse::<Input>()
+            .unwrap();
+        assert_eq!(super::part1(&input), 0);
+        // The vertical line crosses the others at 2,1 and 2,2, the diagonals
+        // cross at 3,1.5, which Bresenham's lines round to 3,2.
+        assert_eq!(super::part2(&input), 2);
+        assert_eq!(part2_bresenham(&input), 3);
+    }
+}
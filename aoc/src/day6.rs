@@ -1,3 +1,6 @@
+use super::error::AocError;
+use super::parse::parse_comma_separated;
+use super::solution::{Implementation, Solution};
 use std::collections::HashMap;
 
 fn evolve(fish_ages: &mut Vec<u8>) {
@@ -7,7 +10,7 @@ fn evolve(fish_ages: &mut Vec<u8>) {
             *i = 6;
             new_generation.push(8);
         } else {
-            *i = *i - 1;
+            *i -= 1;
         }
     }
     fish_ages.append(&mut new_generation);
@@ -40,30 +43,70 @@ pub fn fish_count(fish_by_age: &HashMap<u8, u64>) -> u64 {
     fish_by_age.values().sum()
 }
 
+pub fn to_map(fish_ages: &[u8]) -> HashMap<u8, u64> {
+    let mut result = HashMap::new();
+    for age in fish_ages {
+        result.entry(*age).and_modify(|e| *e += 1).or_insert(1);
+    }
+    result
+}
+
+/// Part 1 simulating every fish.
+fn part1_vec(fish_ages: &[u8]) -> u64 {
+    let mut fish_ages = fish_ages.to_vec();
+    evolve_days(&mut fish_ages, 80);
+    fish_ages.len() as u64
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+    type Input = Vec<u8>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(text: &str) -> Result<Vec<u8>, AocError> {
+        parse_comma_separated(text.trim())
+    }
+
+    fn part1(fish_ages: &Vec<u8>) -> u64 {
+        let mut fish_by_age = to_map(fish_ages);
+        evolve_map_days(&mut fish_by_age, 80);
+        fish_count(&fish_by_age)
+    }
+
+    fn part2(fish_ages: &Vec<u8>) -> u64 {
+        let mut fish_by_age = to_map(fish_ages);
+        evolve_map_days(&mut fish_by_age, 256);
+        fish_count(&fish_by_age)
+    }
+
+    fn part1_impls() -> Vec<Implementation<Vec<u8>, u64>> {
+        vec![("map", Self::part1), ("vec", |ages| part1_vec(ages))]
+    }
+
+    fn part2_impls() -> Vec<Implementation<Vec<u8>, u64>> {
+        vec![("map", Self::part2)]
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use super::super::parse::parse_comma_separated;
-
-    fn to_map(fish_ages: Vec<u8>) -> HashMap<u8, u64> {
-        let mut result = HashMap::new();
-        for age in fish_ages {
-            result.entry(age).and_modify(|e| *e += 1).or_insert(1);
-        }
-        return result;
-    }
+    use crate::input::bundled;
 
     fn get_sample_input() -> Vec<u8> {
-        parse_comma_separated(include_str!("../resources/day6/sample.txt"))
+        parse_comma_separated(&bundled(6, "sample")).unwrap()
     }
 
     fn get_input() -> Vec<u8> {
-        parse_comma_separated(include_str!("../resources/day6/input.txt"))
+        parse_comma_separated(&bundled(6, "real")).unwrap()
     }
 
     #[test]
     fn part1_sample() {
-        let mut fish_ages = to_map(get_sample_input());
+        let mut fish_ages = to_map(&get_sample_input());
         evolve_map_days(&mut fish_ages, 18);
         println!("after 18 days: {}", fish_count(&fish_ages));
         evolve_map_days(&mut fish_ages, 80 - 18);
@@ -72,21 +115,21 @@ mod tests {
 
     #[test]
     fn part1() {
-        let mut fish_ages = to_map(get_input());
+        let mut fish_ages = to_map(&get_input());
         evolve_map_days(&mut fish_ages, 80);
         println!("part1: {}", fish_count(&fish_ages));
     }
 
     #[test]
     fn part2_sample() {
-        let mut fish_ages = to_map(get_sample_input());
+        let mut fish_ages = to_map(&get_sample_input());
         evolve_map_days(&mut fish_ages, 256);
         println!("part2 sample: {}", fish_count(&fish_ages));
     }
 
     #[test]
     fn part2() {
-        let mut fish_ages = to_map(get_input());
+        let mut fish_ages = to_map(&get_input());
         evolve_map_days(&mut fish_ages, 256);
         println!("part2: {}", fish_count(&fish_ages));
     }
@@ -1,19 +1,23 @@
-pub fn part1(positions: &Vec<i32>) -> i32 {
-    solution(&positions, std::convert::identity)
+use super::error::AocError;
+use super::parse::parse_comma_separated;
+use super::solution::Solution;
+
+pub fn part1(positions: &[i32]) -> i32 {
+    solution(positions, std::convert::identity)
 }
 
 fn move_cost(distance: i32) -> i32 {
     distance * (distance + 1) / 2
 }
 
-fn total_distance<F>(positions: &Vec<i32>, position: i32, f: F) -> i32
+fn total_distance<F>(positions: &[i32], position: i32, f: F) -> i32
 where
     F: Fn(i32) -> i32,
 {
     positions.iter().map(|p| f((p - position).abs())).sum()
 }
 
-fn solution<F>(positions: &Vec<i32>, f: F) -> i32
+fn solution<F>(positions: &[i32], f: F) -> i32
 where
     F: Fn(i32) -> i32,
 {
@@ -21,9 +25,9 @@ where
     let mut to = positions.iter().fold(i32::MIN, |a, v| i32::max(a, *v));
     loop {
         let middle = (from + to) / 2;
-        let at_from = total_distance(&positions, from, &f);
-        let at_to = total_distance(&positions, to, &f);
-        let at_middle = total_distance(&positions, middle, &f);
+        let at_from = total_distance(positions, from, &f);
+        let at_to = total_distance(positions, to, &f);
+        let at_middle = total_distance(positions, middle, &f);
 
         if at_middle < at_from && at_middle < at_to {
             // middle might be a solution, cannot exclude it.
@@ -41,20 +45,42 @@ where
         }
     }
 }
-pub fn part2(positions: &Vec<i32>) -> i32 {
-    solution(&positions, move_cost)
+pub fn part2(positions: &[i32]) -> i32 {
+    solution(positions, move_cost)
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+    type Input = Vec<i32>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(text: &str) -> Result<Vec<i32>, AocError> {
+        parse_comma_separated(text.trim())
+    }
+
+    fn part1(positions: &Vec<i32>) -> i32 {
+        part1(positions)
+    }
+
+    fn part2(positions: &Vec<i32>) -> i32 {
+        part2(positions)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::super::parse::parse_comma_separated;
+    use crate::input::bundled;
 
     fn get_sample_input() -> Vec<i32> {
-        parse_comma_separated("16,1,2,0,4,2,7,1,2,14")
+        parse_comma_separated("16,1,2,0,4,2,7,1,2,14").unwrap()
     }
 
     fn get_input() -> Vec<i32> {
-        parse_comma_separated(include_str!("../resources/day7.txt"))
+        parse_comma_separated(&bundled(7, "real")).unwrap()
     }
 
     #[test]
@@ -1,11 +1,13 @@
-use super::parse::parse_line_separated;
+use super::error::AocError;
+use super::parse::{parse_line_separated, split_once};
+use super::solution::Solution;
 use arrayvec::ArrayVec;
+use log::debug;
 use std::collections::HashMap;
 use std::collections::HashSet;
-use std::convert::From;
+use std::fmt;
 use std::result::Result;
 use std::str::FromStr;
-use std::string::ToString;
 
 /// A picture on a 7-digit signal display.
 #[derive(PartialEq, Eq, Hash, Clone)]
@@ -24,7 +26,7 @@ impl Pixel {
         Pixel { value: 1 << index }
     }
     fn all_pixels() -> Vec<Pixel> {
-        (0u8..7u8).map(|i| Pixel::from_index(i)).collect()
+        (0u8..7u8).map(Pixel::from_index).collect()
     }
 }
 impl Picture {
@@ -60,35 +62,40 @@ impl Picture {
     }
 }
 
-impl ToString for Picture {
-    fn to_string(&self) -> String {
-        self.pixels()
-            .into_iter()
-            .map(|p| p.to_string())
-            .collect::<Vec<String>>()
-            .join("")
+impl fmt::Display for Picture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for pixel in self.pixels() {
+            write!(f, "{}", pixel)?;
+        }
+        Ok(())
     }
 }
 
-impl ToString for Pixel {
-    fn to_string(&self) -> String {
-        let mut result = String::new();
+impl fmt::Display for Pixel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         for (i, p) in Pixel::all_pixels().into_iter().enumerate() {
             if *self == p {
-                result.push((U8A + i as u8) as char);
+                write!(f, "{}", (U8A + i as u8) as char)?;
             }
         }
-        result
+        Ok(())
     }
 }
 
-static U8A: u8 = 'a' as u8;
+static U8A: u8 = b'a';
 
 impl FromStr for Picture {
-    type Err = String;
-    fn from_str(text: &str) -> Result<Self, String> {
+    type Err = AocError;
+    fn from_str(text: &str) -> Result<Self, AocError> {
         let mut pixels = 0u8;
-        for ch in text.chars() {
+        for (i, ch) in text.char_indices() {
+            if !('a'..='g').contains(&ch) {
+                return Err(AocError::parse(
+                    text,
+                    &text[i..i + ch.len_utf8()],
+                    "expected segment a-g",
+                ));
+            }
             pixels |= 1 << ((ch as u8) - U8A);
         }
         Ok(Picture { pixels })
@@ -101,27 +108,29 @@ struct Display {
     display: [Picture; 4],
 }
 
+/// Parses exactly `N` space separated pictures from `text`, a slice of
+/// `source`.
+fn parse_pictures<const N: usize>(source: &str, text: &str) -> Result<[Picture; N], AocError> {
+    let mut pictures = ArrayVec::<Picture, N>::new();
+    for s in text.split(' ') {
+        let picture = s.parse::<Picture>().map_err(|e| e.within(source, s))?;
+        pictures
+            .try_push(picture)
+            .map_err(|_| AocError::parse(source, s, format!("expected {} pictures", N)))?;
+    }
+    pictures
+        .into_inner()
+        .map_err(|_| AocError::parse(source, text, format!("expected {} pictures", N)))
+}
+
 impl FromStr for Display {
-    type Err = String;
+    type Err = AocError;
 
-    fn from_str(text: &str) -> Result<Self, String> {
-        let (digits_str, display_str) = text
-            .trim()
-            .split_once(" | ")
-            .ok_or(format!("Cannot split by |: {}", text))?;
+    fn from_str(text: &str) -> Result<Self, AocError> {
+        let (digits_str, display_str) = split_once(text, text.trim(), " | ")?;
         Ok(Display {
-            all_digits: digits_str
-                .split(" ")
-                .map(|s| s.parse::<Picture>().unwrap())
-                .collect::<ArrayVec<Picture, 10>>()
-                .into_inner()
-                .map_err(|v| format!("wrong length: {}", v.len()))?,
-            display: display_str
-                .split(" ")
-                .map(|s| s.parse::<Picture>().unwrap())
-                .collect::<ArrayVec<Picture, 4>>()
-                .into_inner()
-                .map_err(|v| format!("wrong length: {}", v.len()))?,
+            all_digits: parse_pictures(text, digits_str)?,
+            display: parse_pictures(text, display_str)?,
         })
     }
 }
@@ -130,9 +139,9 @@ pub struct Input {
 }
 
 impl FromStr for Input {
-    type Err = String;
+    type Err = AocError;
 
-    fn from_str(text: &str) -> Result<Input, String> {
+    fn from_str(text: &str) -> Result<Input, AocError> {
         Ok(Input {
             displays: parse_line_separated(text.trim())?,
         })
@@ -154,17 +163,6 @@ fn get_true_digits() -> Vec<Picture> {
     ]
 }
 
-#[derive(Eq, PartialEq, Debug, Clone, Hash)]
-struct Segment {
-    index: u8,
-}
-
-impl From<u8> for Segment {
-    fn from(val: u8) -> Self {
-        Segment { index: val }
-    }
-}
-
 fn decode_display(display: &Display) -> u32 {
     let true_pics: HashMap<Picture, u8> = get_true_digits()
         .into_iter()
@@ -192,11 +190,11 @@ fn decode_display(display: &Display) -> u32 {
         .map(|p| true_pics.get(&p).cloned())
         .fold(0u32, |acc, o| acc * 10 + (o.unwrap() as u32));
 
-    return substituted_display;
+    substituted_display
 }
 
 pub fn part2(input: &Input) -> u32 {
-    input.displays.iter().map(|d| decode_display(d)).sum()
+    input.displays.iter().map(decode_display).sum()
 }
 
 fn try_candidates(
@@ -229,7 +227,7 @@ fn try_candidates(
                 .collect();
         }
         if new_v.is_empty() {
-            println!("No candidates for {}, falling back", new_k.to_string());
+            debug!("No candidates for {}, falling back", new_k);
             return None;
         }
         // if new_k.pixel_count() == 0 {
@@ -265,7 +263,7 @@ fn try_candidates(
         }
     }
 
-    return None;
+    None
 }
 
 fn is_valid_substitution(
@@ -274,17 +272,17 @@ fn is_valid_substitution(
     to: &Pixel,
 ) -> bool {
     for (k, v) in pics {
-        if k.contains(&from) {
-            if v.iter().all(|d| !d.contains(&to)) {
+        if k.contains(from) {
+            if v.iter().all(|d| !d.contains(to)) {
                 return false;
             }
         } else {
-            if v.iter().all(|d| d.contains(&to)) {
+            if v.iter().all(|d| d.contains(to)) {
                 return false;
             }
         }
     }
-    return true;
+    true
 }
 
 pub fn part1(input: &Input) -> u32 {
@@ -297,21 +295,39 @@ pub fn part1(input: &Input) -> u32 {
             }
         }
     }
-    return result;
+    result
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+    type Input = Input;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(text: &str) -> Result<Input, AocError> {
+        text.parse()
+    }
+
+    fn part1(input: &Input) -> u32 {
+        part1(input)
+    }
+
+    fn part2(input: &Input) -> u32 {
+        part2(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::input::bundled;
     fn sample_input() -> Input {
-        include_str!("../resources/day8_sample.txt")
-            .parse::<Input>()
-            .unwrap()
+        bundled(8, "sample").parse::<Input>().unwrap()
     }
 
     fn input() -> Input {
-        include_str!("../resources/day8.txt")
-            .parse::<Input>()
-            .unwrap()
+        bundled(8, "real").parse::<Input>().unwrap()
     }
 
     use super::*;
@@ -320,6 +336,14 @@ mod tests {
         let digit = "agf".parse::<Picture>().unwrap();
         assert_eq!(digit.pixel_count(), 3);
         assert_eq!(digit.to_string(), "afg");
+        assert_eq!(
+            "ab cd | ax".parse::<Display>().err().unwrap().to_string(),
+            "line 1 col 1: expected 10 pictures, found \"ab cd\""
+        );
+        assert_eq!(
+            "ab cd".parse::<Display>().err().unwrap().to_string(),
+            "line 1 col 6: expected \" | \""
+        );
     }
 
     #[test]
@@ -0,0 +1,276 @@
+use std::fmt;
+use std::num::ParseIntError;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AocError {
+    /// Malformed puzzle input. Lines and columns are 1-based, line 0 means
+    /// the position is not known yet. `text` is the offending part of the
+    /// input.
+    Parse {
+        day: Option<u8>,
+        line: usize,
+        column: usize,
+        text: String,
+        message: String,
+    },
+    /// Input file that does not exist in the input directory.
+    MissingInput {
+        day: u8,
+        variant: String,
+        path: PathBuf,
+    },
+    Io {
+        path: PathBuf,
+        message: String,
+    },
+    Config {
+        path: PathBuf,
+        message: String,
+    },
+    NoSolution {
+        day: u8,
+        part: Option<u8>,
+    },
+    /// Day that has no implementation of the requested name.
+    NoImplementation {
+        day: u8,
+        part: Option<u8>,
+        name: String,
+    },
+    /// Part that panicked while parsing or solving.
+    Panic {
+        day: u8,
+        part: u8,
+        message: String,
+    },
+    /// Runs that could not produce an answer.
+    RunFailed {
+        failed: usize,
+    },
+    /// Answers that do not match the recorded ones.
+    Verification {
+        failed: usize,
+    },
+    /// Benchmark times slower than the baseline.
+    Regression {
+        baseline: String,
+        regressed: usize,
+    },
+}
+
+/// Returns the 1-based line and column at which `span` starts in `source`,
+/// or `None` if `span` is not a slice of `source`.
+fn position(source: &str, span: &str) -> Option<(usize, usize)> {
+    let start = source.as_ptr() as usize;
+    let offset = (span.as_ptr() as usize).checked_sub(start)?;
+    if offset > source.len() {
+        return None;
+    }
+    let before = &source[..offset];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    Some((
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+    ))
+}
+
+impl AocError {
+    /// Parse error at `span`, which must be a slice of `source`.
+    pub fn parse<M: Into<String>>(source: &str, span: &str, message: M) -> Self {
+        let (line, column) = position(source, span).unwrap_or((1, 1));
+        AocError::Parse {
+            day: None,
+            line,
+            column,
+            text: span.to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// Parse error for a value that was parsed from `inner`, a slice of
+    /// `outer`: moves the error position to be relative to `outer`.
+    pub fn within(self, outer: &str, inner: &str) -> Self {
+        match self {
+            AocError::Parse {
+                day,
+                line,
+                column,
+                text,
+                message,
+            } => {
+                let (inner_line, inner_column) = position(outer, inner).unwrap_or((1, 1));
+                if line == 0 {
+                    // The whole of `inner` is at fault.
+                    return AocError::Parse {
+                        day,
+                        line: inner_line,
+                        column: inner_column,
+                        text: inner.to_string(),
+                        message,
+                    };
+                }
+                AocError::Parse {
+                    day,
+                    line: line + inner_line - 1,
+                    column: if line == 1 {
+                        column + inner_column - 1
+                    } else {
+                        column
+                    },
+                    text,
+                    message,
+                }
+            }
+            other => other,
+        }
+    }
+
+    pub fn in_day(self, day: u8) -> Self {
+        match self {
+            AocError::Parse {
+                line,
+                column,
+                text,
+                message,
+                ..
+            } => AocError::Parse {
+                day: Some(day),
+                line,
+                column,
+                text,
+                message,
+            },
+            other => other,
+        }
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AocError::Parse {
+                day,
+                line,
+                column,
+                text,
+                message,
+            } => {
+                match (day, line) {
+                    (Some(day), 0) => write!(f, "day{}: ", day)?,
+                    (Some(day), _) => write!(f, "day{} line {} col {}: ", day, line, column)?,
+                    (None, 0) => {}
+                    (None, _) => write!(f, "line {} col {}: ", line, column)?,
+                }
+                write!(f, "{}", message)?;
+                if !text.is_empty() {
+                    write!(f, ", found {:?}", text)?;
+                }
+                Ok(())
+            }
+            AocError::MissingInput { day, variant, path } => write!(
+                f,
+                "no {} input for day {}: {} does not exist, set AOC_INPUT_DIR to use another directory",
+                variant,
+                day,
+                path.display()
+            ),
+            AocError::Io { path, message } => {
+                write!(f, "cannot read {}: {}", path.display(), message)
+            }
+            AocError::Config { path, message } => {
+                write!(f, "invalid config {}: {}", path.display(), message)
+            }
+            AocError::NoSolution { day, part: None } => write!(f, "no solution for day {}", day),
+            AocError::NoSolution {
+                day,
+                part: Some(part),
+            } => write!(f, "no solution for day {} part {}", day, part),
+            AocError::NoImplementation {
+                day,
+                part: None,
+                name,
+            } => write!(f, "no implementation {} for day {}", name, day),
+            AocError::NoImplementation {
+                day,
+                part: Some(part),
+                name,
+            } => write!(f, "no implementation {} for day {} part {}", name, day, part),
+            AocError::Panic { day, part, message } => {
+                write!(f, "day {} part {} panicked: {}", day, part, message)
+            }
+            AocError::RunFailed { failed } => write!(f, "{} runs failed", failed),
+            AocError::Verification { failed } => write!(f, "{} answers failed verification", failed),
+            AocError::Regression {
+                baseline,
+                regressed,
+            } => write!(
+                f,
+                "{} times regressed compared to baseline {}",
+                regressed, baseline
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AocError {}
+
+/// Errors of primitive parsers carry no position, [AocError::within] adds it.
+impl From<ParseIntError> for AocError {
+    fn from(e: ParseIntError) -> Self {
+        AocError::Parse {
+            day: None,
+            line: 0,
+            column: 0,
+            text: String::new(),
+            message: e.to_string(),
+        }
+    }
+}
+
+impl From<String> for AocError {
+    fn from(message: String) -> Self {
+        AocError::Parse {
+            day: None,
+            line: 0,
+            column: 0,
+            text: String::new(),
+            message,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn position_test() {
+        let text = "on x=1..2\noff x=3..y";
+        let (_, second) = text.split_once('\n').unwrap();
+        let error = AocError::parse(text, &second[9..], "expected integer");
+        assert_eq!(
+            error.in_day(22).to_string(),
+            "day22 line 2 col 10: expected integer, found \"y\""
+        );
+    }
+
+    #[test]
+    fn within_test() {
+        let text = "1,2\n3,x";
+        let line = text.lines().nth(1).unwrap();
+        let error = AocError::parse(line, &line[2..], "expected integer").within(text, line);
+        assert_eq!(
+            error.to_string(),
+            "line 2 col 3: expected integer, found \"x\""
+        );
+
+        let error = AocError::from("x".parse::<u8>().unwrap_err());
+        assert_eq!(error.to_string(), "invalid digit found in string");
+        let error = error.within(text, line);
+        assert_eq!(
+            error.to_string(),
+            "line 2 col 1: invalid digit found in string, found \"3,x\""
+        );
+    }
+}
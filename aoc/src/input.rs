@@ -0,0 +1,194 @@
+//! Puzzle inputs are resolved at runtime from an input directory laid out as
+//! `<dir>/2021/day19.txt` for the real input and `<dir>/2021/day19_sample.txt`
+//! for a sample named `sample`.
+//!
+//! The directory is taken from the `AOC_INPUT_DIR` environment variable, then
+//! from the `input_dir` key of the config file (`AOC_CONFIG`, or
+//! `~/.config/aoc/config.toml`), and falls back to the inputs bundled in the
+//! repository.
+use super::error::AocError;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+pub const YEAR: u16 = 2021;
+
+/// Which input of a day to load.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Variant {
+    /// The real puzzle input.
+    Real,
+    /// A named sample, usually from the puzzle description.
+    Sample(String),
+}
+
+impl Variant {
+    pub fn sample() -> Self {
+        Variant::Sample("sample".to_string())
+    }
+
+    fn file_name(&self, day: u8) -> String {
+        match self {
+            Variant::Real => format!("day{}.txt", day),
+            Variant::Sample(name) => format!("day{}_{}.txt", day, name),
+        }
+    }
+}
+
+impl fmt::Display for Variant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Variant::Real => write!(f, "real"),
+            Variant::Sample(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+impl FromStr for Variant {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, String> {
+        match text {
+            "" => Err("Empty input name".to_string()),
+            "real" => Ok(Variant::Real),
+            name => Ok(Variant::Sample(name.to_string())),
+        }
+    }
+}
+
+pub struct InputProvider {
+    dir: PathBuf,
+}
+
+impl InputProvider {
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        InputProvider { dir: dir.into() }
+    }
+
+    /// Provider for the inputs checked into the repository.
+    pub fn bundled() -> Self {
+        InputProvider::new(concat!(env!("CARGO_MANIFEST_DIR"), "/resources"))
+    }
+
+    /// Provider for the directory configured through the environment or the
+    /// config file, see the module documentation.
+    pub fn from_env() -> Result<Self, AocError> {
+        if let Some(dir) = std::env::var_os("AOC_INPUT_DIR") {
+            return Ok(InputProvider::new(dir));
+        }
+        match config_path() {
+            Some(path) if path.exists() => Ok(read_config(&path)?
+                .map(InputProvider::new)
+                .unwrap_or_else(InputProvider::bundled)),
+            _ => Ok(InputProvider::bundled()),
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn path(&self, day: u8, variant: &Variant) -> PathBuf {
+        self.dir.join(YEAR.to_string()).join(variant.file_name(day))
+    }
+
+    pub fn load(&self, day: u8, variant: &Variant) -> Result<String, AocError> {
+        let path = self.path(day, variant);
+        if !path.exists() {
+            return Err(AocError::MissingInput {
+                day,
+                variant: variant.to_string(),
+                path,
+            });
+        }
+        std::fs::read_to_string(&path).map_err(|e| AocError::Io {
+            message: e.to_string(),
+            path,
+        })
+    }
+}
+
+fn config_path() -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os("AOC_CONFIG") {
+        return Some(PathBuf::from(path));
+    }
+    let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".config")))?;
+    Some(config_dir.join("aoc").join("config.toml"))
+}
+
+fn read_config(path: &Path) -> Result<Option<PathBuf>, AocError> {
+    let text = std::fs::read_to_string(path).map_err(|e| AocError::Io {
+        path: path.to_path_buf(),
+        message: e.to_string(),
+    })?;
+    let config = text.parse::<toml::Table>().map_err(|e| AocError::Config {
+        path: path.to_path_buf(),
+        message: e.to_string(),
+    })?;
+    match config.get("input_dir") {
+        None => Ok(None),
+        Some(toml::Value::String(dir)) => Ok(Some(PathBuf::from(dir))),
+        Some(_) => Err(AocError::Config {
+            path: path.to_path_buf(),
+            message: "input_dir must be a string".to_string(),
+        }),
+    }
+}
+
+/// Loads an input bundled with the repository, panicking if it is missing.
+#[cfg(test)]
+pub fn bundled(day: u8, variant: &str) -> String {
+    InputProvider::bundled()
+        .load(day, &variant.parse().unwrap())
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_test() {
+        let provider = InputProvider::new("/inputs");
+        assert_eq!(
+            provider.path(19, &Variant::Real),
+            Path::new("/inputs/2021/day19.txt")
+        );
+        assert_eq!(
+            provider.path(22, &"sample2".parse().unwrap()),
+            Path::new("/inputs/2021/day22_sample2.txt")
+        );
+    }
+
+    #[test]
+    fn load_test() {
+        assert!(InputProvider::bundled()
+            .load(7, &Variant::sample())
+            .unwrap()
+            .starts_with("16,1,2"));
+
+        let error = InputProvider::new("/nonexistent")
+            .load(3, &Variant::Real)
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "no real input for day 3: /nonexistent/2021/day3.txt does not exist, \
+             set AOC_INPUT_DIR to use another directory"
+        );
+    }
+
+    #[test]
+    fn config_test() {
+        let path = std::env::temp_dir().join(format!("aoc-config-{}.toml", std::process::id()));
+        std::fs::write(&path, "input_dir = \"/home/me/aoc\"\n").unwrap();
+        assert_eq!(
+            read_config(&path).unwrap(),
+            Some(PathBuf::from("/home/me/aoc"))
+        );
+        std::fs::write(&path, "input_dir = 1\n").unwrap();
+        assert!(read_config(&path).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,264 @@
+//! Diagnostic output of solutions, written with the `log` macros. Each day
+//! logs to its own target, `day22` for example, and nothing is logged unless
+//! a [Filter] enables it:
+//!
+//! ```text
+//! day22=debug,day17=trace
+//! ```
+//!
+//! Records are written to stderr, unless the run is [capture]d, so that runs
+//! on several threads do not interleave. The same goes for panic messages
+//! inside [capture_panics].
+use log::{LevelFilter, Log, Metadata, Record};
+use std::any::Any;
+use std::cell::RefCell;
+use std::panic::{self, PanicHookInfo};
+use std::str::FromStr;
+use std::sync::{Mutex, RwLock};
+
+/// Levels to log at: a default level and levels of targets, which match the
+/// target itself and its submodules.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Filter {
+    default: LevelFilter,
+    targets: Vec<(String, LevelFilter)>,
+}
+
+impl Filter {
+    /// Filter that logs nothing.
+    pub const fn off() -> Self {
+        Filter {
+            default: LevelFilter::Off,
+            targets: Vec::new(),
+        }
+    }
+
+    pub fn level(&self, target: &str) -> LevelFilter {
+        let target = short_target(target);
+        self.targets
+            .iter()
+            .rev()
+            .find(|(t, _)| {
+                target
+                    .strip_prefix(t.as_str())
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+            })
+            .map_or(self.default, |(_, level)| *level)
+    }
+
+    /// Most verbose level of any target.
+    pub fn max_level(&self) -> LevelFilter {
+        self.targets
+            .iter()
+            .map(|(_, level)| *level)
+            .fold(self.default, Ord::max)
+    }
+}
+
+/// Parses comma separated `target=level` pairs, a bare level sets the default
+/// level.
+impl FromStr for Filter {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, String> {
+        let mut filter = Filter::off();
+        for directive in text.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+            let parse_level = |level: &str| {
+                level
+                    .parse::<LevelFilter>()
+                    .map_err(|_| format!("unknown log level {:?}", level))
+            };
+            match directive.split_once('=') {
+                Some((target, level)) => filter
+                    .targets
+                    .push((target.to_string(), parse_level(level)?)),
+                None => filter.default = parse_level(directive)?,
+            }
+        }
+        Ok(filter)
+    }
+}
+
+/// Target without the crate name, `day22` for `aoc::day22`.
+fn short_target(target: &str) -> &str {
+    target.strip_prefix("aoc::").unwrap_or(target)
+}
+
+struct Logger {
+    filter: RwLock<Filter>,
+}
+
+static LOGGER: Logger = Logger {
+    filter: RwLock::new(Filter::off()),
+};
+
+thread_local! {
+    static CAPTURED: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.filter.read().unwrap().level(metadata.target())
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            write_record(record);
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+fn write_record(record: &Record) {
+    let line = format!(
+        "{} {}: {}",
+        record.level(),
+        short_target(record.target()),
+        record.args()
+    );
+    CAPTURED.with(|c| match c.borrow_mut().as_mut() {
+        Some(output) => {
+            output.push_str(&line);
+            output.push('\n');
+        }
+        None => eprintln!("{}", line),
+    })
+}
+
+/// Logs records that pass `filter` from now on.
+pub fn init(filter: Filter) {
+    // Fails if the logger is already set, which only changes the filter.
+    let _ = log::set_logger(&LOGGER);
+    log::set_max_level(filter.max_level());
+    *LOGGER.filter.write().unwrap() = filter;
+}
+
+/// Calls `f` and returns its result with everything it logged on this
+/// thread.
+pub fn capture<T>(f: impl FnOnce() -> T) -> (T, String) {
+    let previous = CAPTURED.with(|c| c.replace(Some(String::new())));
+    let result = f();
+    let output = CAPTURED.with(|c| c.replace(previous)).unwrap_or_default();
+    (result, output)
+}
+
+type PanicHook = Box<dyn Fn(&PanicHookInfo<'_>) + Sync + Send>;
+
+/// Number of running [capture_panics] calls and the hook they replaced.
+static PANIC_HOOK: Mutex<(usize, Option<PanicHook>)> = Mutex::new((0, None));
+
+/// Text of a panic payload, which is a string unless the panic was raised
+/// with `panic_any`.
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic payload".to_string()
+    }
+}
+
+/// Writes the panic message to the output of a [capture]d thread, leaves
+/// other threads to the replaced hook.
+fn write_panic(info: &PanicHookInfo<'_>) {
+    let line = match info.location() {
+        Some(location) => format!(
+            "panicked at {}: {}",
+            location,
+            panic_message(info.payload())
+        ),
+        None => format!("panicked: {}", panic_message(info.payload())),
+    };
+    let captured = CAPTURED.with(|c| match c.try_borrow_mut().as_deref_mut() {
+        Ok(Some(output)) => {
+            output.push_str(&line);
+            output.push('\n');
+            true
+        }
+        _ => false,
+    });
+    if !captured {
+        let hook = PANIC_HOOK.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(previous) = &hook.1 {
+            previous(info);
+        }
+    }
+}
+
+/// Restores the panic hook when the last [capture_panics] call ends.
+struct PanicHookGuard;
+
+impl Drop for PanicHookGuard {
+    fn drop(&mut self) {
+        // Setting a hook while panicking fails, leave it installed then.
+        if std::thread::panicking() {
+            return;
+        }
+        let mut hook = PANIC_HOOK.lock().unwrap_or_else(|e| e.into_inner());
+        hook.0 -= 1;
+        if hook.0 == 0 {
+            if let Some(previous) = hook.1.take() {
+                panic::set_hook(previous);
+            }
+        }
+    }
+}
+
+/// Calls `f` with panic messages of [capture]d threads written to their
+/// output instead of stderr.
+pub fn capture_panics<T>(f: impl FnOnce() -> T) -> T {
+    {
+        let mut hook = PANIC_HOOK.lock().unwrap_or_else(|e| e.into_inner());
+        if hook.0 == 0 {
+            hook.1 = Some(panic::take_hook());
+            panic::set_hook(Box::new(write_panic));
+        }
+        hook.0 += 1;
+    }
+    let _guard = PanicHookGuard;
+    f()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use log::Level;
+
+    #[test]
+    fn filter_test() {
+        let filter: Filter = "warn, day22=debug,day2=trace".parse().unwrap();
+        assert_eq!(filter.level("aoc::day22"), LevelFilter::Debug);
+        assert_eq!(filter.level("aoc::day2"), LevelFilter::Trace);
+        assert_eq!(filter.level("aoc::day21"), LevelFilter::Warn);
+        assert_eq!(filter.max_level(), LevelFilter::Trace);
+        assert_eq!("".parse::<Filter>().unwrap(), Filter::off());
+        assert_eq!(
+            "day22=loud".parse::<Filter>().unwrap_err(),
+            "unknown log level \"loud\""
+        );
+    }
+
+    #[test]
+    fn capture_test() {
+        let record = |message| {
+            write_record(
+                &Record::builder()
+                    .level(Level::Debug)
+                    .target("aoc::day22")
+                    .args(format_args!("{}", message))
+                    .build(),
+            )
+        };
+        let (result, output) = capture(|| {
+            record("a");
+            let ((), inner) = capture(|| record("b"));
+            assert_eq!(inner, "DEBUG day22: b\n");
+            record("c");
+            2
+        });
+        assert_eq!(result, 2);
+        assert_eq!(output, "DEBUG day22: a\nDEBUG day22: c\n");
+    }
+}
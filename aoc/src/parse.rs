@@ -1,15 +1,107 @@
-pub fn parse_comma_separated<T>(text: &str) -> Vec<T>
+use super::error::AocError;
+use std::str::FromStr;
+
+pub fn parse_comma_separated<T>(text: &str) -> Result<Vec<T>, AocError>
 where
-    T: std::str::FromStr,
-    T::Err: std::fmt::Debug,
+    T: FromStr,
+    T::Err: Into<AocError>,
 {
-    text.split(",").map(|s| s.parse::<T>().unwrap()).collect()
+    text.split(',')
+        .map(str::trim)
+        .map(|s| s.parse::<T>().map_err(|e| e.into().within(text, s)))
+        .collect()
 }
 
-pub fn parse_line_separated<T>(text: &str) -> Result<Vec<T>, T::Err>
+pub fn parse_line_separated<T>(text: &str) -> Result<Vec<T>, AocError>
 where
-    T: std::str::FromStr,
-    T::Err: std::fmt::Debug,
+    T: FromStr,
+    T::Err: Into<AocError>,
 {
-    text.lines().map(|s| s.parse::<T>()).collect()
+    text.lines()
+        .map(|s| s.parse::<T>().map_err(|e| e.into().within(text, s)))
+        .collect()
+}
+
+/// Parses `token`, a slice of `source`, reporting `expected` at its position
+/// on failure.
+pub fn parse_token<T: FromStr>(source: &str, token: &str, expected: &str) -> Result<T, AocError> {
+    token
+        .parse::<T>()
+        .map_err(|_| AocError::parse(source, token, format!("expected {}", expected)))
+}
+
+/// Like `str::split_once`, but reports a missing separator at the end of
+/// `text`, a slice of `source`.
+pub fn split_once<'a>(
+    source: &str,
+    text: &'a str,
+    separator: &str,
+) -> Result<(&'a str, &'a str), AocError> {
+    text.split_once(separator).ok_or_else(|| {
+        AocError::parse(
+            source,
+            &text[text.len()..],
+            format!("expected {:?}", separator),
+        )
+    })
+}
+
+/// Parses an inclusive range like `x=-20..26` in `text`, a slice of
+/// `source`.
+pub fn parse_axis_range<T: FromStr>(
+    source: &str,
+    text: &str,
+    axis: &str,
+) -> Result<(T, T), AocError> {
+    let (name, range) = split_once(source, text, "=")?;
+    if name != axis {
+        return Err(AocError::parse(
+            source,
+            name,
+            format!("expected axis {}", axis),
+        ));
+    }
+    let (from, to) = split_once(source, range, "..")?;
+    Ok((
+        parse_token(source, from, "integer")?,
+        parse_token(source, to, "integer")?,
+    ))
+}
+
+/// Parses a line of single decimal digits, such as `2199943210`.
+pub fn parse_digits(line: &str) -> Result<Vec<u8>, AocError> {
+    line.char_indices()
+        .map(|(i, ch)| {
+            ch.to_digit(10)
+                .map(|d| d as u8)
+                .ok_or_else(|| AocError::parse(line, &line[i..i + ch.len_utf8()], "expected digit"))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_test() {
+        assert_eq!(
+            parse_comma_separated::<u8>("3,4, 3").unwrap(),
+            vec![3, 4, 3]
+        );
+        assert_eq!(
+            parse_comma_separated::<u8>("3,x").unwrap_err().to_string(),
+            "line 1 col 3: invalid digit found in string, found \"x\""
+        );
+        assert_eq!(
+            parse_line_separated::<i64>("1\n2\n-\n")
+                .unwrap_err()
+                .to_string(),
+            "line 3 col 1: invalid digit found in string, found \"-\""
+        );
+        assert_eq!(
+            parse_digits("12a").unwrap_err().to_string(),
+            "line 1 col 3: expected digit, found \"a\""
+        );
+    }
 }
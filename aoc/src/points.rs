@@ -1,90 +1,654 @@
-use std::ops::{Add, AddAssign};
+//! Points and vectors on 2D and 3D grids, generic over the coordinate type,
+//! which is `i32` unless given.
+pub mod direction;
+pub mod line;
+pub mod symmetry;
+
+use super::error::AocError;
+use super::parse::{parse_token, split_once};
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
 use std::str::FromStr;
 
-#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
-pub struct Point {
-    pub x: i32,
-    pub y: i32,
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy, Default, PartialOrd, Ord)]
+pub struct Point<T = i32> {
+    pub x: T,
+    pub y: T,
 }
 
-impl Point {
-    pub fn new() -> Self {
-        Point { x: 0, y: 0 }
+/// Difference of two points, a point seen as a vector.
+pub type Offset<T = i32> = Point<T>;
+
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy, Default, PartialOrd, Ord)]
+pub struct Point3<T = i32> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Point { x, y }
+    }
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Point3 { x, y, z }
+    }
+}
+
+fn abs_diff<T: Ord + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+/// Operators, distances and conversions shared by [Point] and [Point3].
+macro_rules! point_impls {
+    ($name:ident, $($field:ident),+) => {
+        impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> $name<T> {
+            /// Sum of the distances along each axis.
+            pub fn manhattan(self, other: Self) -> T {
+                [$(abs_diff(self.$field, other.$field)),+]
+                    .into_iter()
+                    .reduce(|a, b| a + b)
+                    .unwrap()
+            }
+
+            /// Largest distance along an axis.
+            pub fn chebyshev(self, other: Self) -> T {
+                [$(abs_diff(self.$field, other.$field)),+]
+                    .into_iter()
+                    .max()
+                    .unwrap()
+            }
+        }
+
+        impl<T> $name<T> {
+            /// Converts the coordinates to a wider type.
+            pub fn convert<U: From<T>>(self) -> $name<U> {
+                $name { $($field: U::from(self.$field)),+ }
+            }
+
+            /// Converts the coordinates to another type, `None` if any does
+            /// not fit.
+            pub fn try_convert<U: TryFrom<T>>(self) -> Option<$name<U>> {
+                Some($name { $($field: U::try_from(self.$field).ok()?),+ })
+            }
+        }
+
+        impl<T: Add<Output = T>> Add for $name<T> {
+            type Output = Self;
+            fn add(self, rhs: Self) -> Self {
+                $name { $($field: self.$field + rhs.$field),+ }
+            }
+        }
+
+        impl<T: Sub<Output = T>> Sub for $name<T> {
+            type Output = Self;
+            fn sub(self, rhs: Self) -> Self {
+                $name { $($field: self.$field - rhs.$field),+ }
+            }
+        }
+
+        impl<T: Neg<Output = T>> Neg for $name<T> {
+            type Output = Self;
+            fn neg(self) -> Self {
+                $name { $($field: -self.$field),+ }
+            }
+        }
+
+        /// Scaling by a factor.
+        impl<T: Copy + Mul<Output = T>> Mul<T> for $name<T> {
+            type Output = Self;
+            fn mul(self, rhs: T) -> Self {
+                $name { $($field: self.$field * rhs),+ }
+            }
+        }
+
+        impl<T: AddAssign> AddAssign for $name<T> {
+            fn add_assign(&mut self, rhs: Self) {
+                $(self.$field += rhs.$field;)+
+            }
+        }
+
+        impl<T: SubAssign> SubAssign for $name<T> {
+            fn sub_assign(&mut self, rhs: Self) {
+                $(self.$field -= rhs.$field;)+
+            }
+        }
+    };
+}
+
+point_impls!(Point, x, y);
+point_impls!(Point3, x, y, z);
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Point { x, y }
     }
 }
+
+impl<T> From<Point<T>> for (T, T) {
+    fn from(point: Point<T>) -> Self {
+        (point.x, point.y)
+    }
+}
+
+impl<T> From<(T, T, T)> for Point3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Point3 { x, y, z }
+    }
+}
+
+impl<T> From<Point3<T>> for (T, T, T) {
+    fn from(point: Point3<T>) -> Self {
+        (point.x, point.y, point.z)
+    }
+}
+
+/// Writes `x,y`, the format [Point::from_str] parses.
+impl<T: fmt::Display> fmt::Display for Point<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+/// Writes `x,y,z`, the format [Point3::from_str] parses.
+impl<T: fmt::Display> fmt::Display for Point3<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+/// Direction the y axis points to, which decides what the top of a [Rect]
+/// is. Maps read from text are [YDown](Orientation::YDown).
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub enum Orientation {
+    /// Rows are numbered from top to bottom, as in text.
+    YDown,
+    /// The y axis points up, as in math.
+    YUp,
+}
+
+/// Rectangle of the points between two corners, both inclusive.
+///
+/// Fields cropped to a rectangle keep the points inside it at their
+/// coordinates, parts of the rectangle outside of the field are left out.
 #[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
 pub struct Rect {
-    pub top_left: Point,
-    pub bottom_right: Point,
+    /// Corner with the smallest coordinates.
+    pub min: Point,
+    /// Corner with the largest coordinates.
+    pub max: Point,
 }
 
 impl Rect {
+    /// Rectangle with corners `a` and `b`, which may be any opposite corners.
+    pub fn new(a: Point, b: Point) -> Rect {
+        Rect {
+            min: Point::new(a.x.min(b.x), a.y.min(b.y)),
+            max: Point::new(a.x.max(b.x), a.y.max(b.y)),
+        }
+    }
+
+    /// Smallest rectangle containing all `points`, `None` if there are none.
+    pub fn from_points(points: impl IntoIterator<Item = Point>) -> Option<Rect> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        Some(points.fold(Rect::new(first, first), |rect, p| {
+            rect.union(&Rect::new(p, p))
+        }))
+    }
+
+    pub fn width(&self) -> u32 {
+        self.min.x.abs_diff(self.max.x) + 1
+    }
+
+    pub fn height(&self) -> u32 {
+        self.min.y.abs_diff(self.max.y) + 1
+    }
+
+    /// Number of points in the rectangle.
+    pub fn area(&self) -> u64 {
+        self.width() as u64 * self.height() as u64
+    }
+
     pub fn contains(&self, point: &Point) -> bool {
-        point.x <= self.bottom_right.x
-            && point.x >= self.top_left.x
-            && point.y <= self.top_left.y
-            && point.y >= self.bottom_right.y
+        (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+    }
+
+    /// Points in both rectangles, `None` if they do not overlap.
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        let min = Point::new(self.min.x.max(other.min.x), self.min.y.max(other.min.y));
+        let max = Point::new(self.max.x.min(other.max.x), self.max.y.min(other.max.y));
+        (min.x <= max.x && min.y <= max.y).then_some(Rect { min, max })
+    }
+
+    /// Smallest rectangle containing both rectangles.
+    pub fn union(&self, other: &Rect) -> Rect {
+        Rect {
+            min: Point::new(self.min.x.min(other.min.x), self.min.y.min(other.min.y)),
+            max: Point::new(self.max.x.max(other.max.x), self.max.y.max(other.max.y)),
+        }
+    }
+
+    /// Rectangle grown by `margin` on every side.
+    pub fn expand(&self, margin: u32) -> Rect {
+        let margin = Offset::new(margin as i32, margin as i32);
+        Rect {
+            min: self.min - margin,
+            max: self.max + margin,
+        }
+    }
+
+    /// Y coordinate of the top edge.
+    pub fn top(&self, orientation: Orientation) -> i32 {
+        match orientation {
+            Orientation::YDown => self.min.y,
+            Orientation::YUp => self.max.y,
+        }
+    }
+
+    /// Y coordinate of the bottom edge.
+    pub fn bottom(&self, orientation: Orientation) -> i32 {
+        match orientation {
+            Orientation::YDown => self.max.y,
+            Orientation::YUp => self.min.y,
+        }
+    }
+
+    pub fn top_left(&self, orientation: Orientation) -> Point {
+        Point::new(self.min.x, self.top(orientation))
+    }
+
+    pub fn bottom_right(&self, orientation: Orientation) -> Point {
+        Point::new(self.max.x, self.bottom(orientation))
+    }
+
+    /// Points of the rectangle row by row, from the smallest y.
+    pub fn points(&self) -> RectPoints {
+        RectPoints {
+            rect: *self,
+            next: Some(self.min),
+        }
     }
 }
-#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
-pub struct Offset(pub i32, pub i32);
-
-static TOP: Offset = Offset(0, -1);
-static TOP_LEFT: Offset = Offset(-1, -1);
-static TOP_RIGHT: Offset = Offset(1, -1);
-static BOTTOM: Offset = Offset(0, 1);
-static BOTTOM_LEFT: Offset = Offset(-1, 1);
-static BOTTOM_RIGHT: Offset = Offset(1, 1);
-static LEFT: Offset = Offset(-1, 0);
-static RIGHT: Offset = Offset(1, 0);
-
-pub fn neighbors(point: &Point, diagonals: bool, width: u32, height: u32) -> Vec<Point> {
-    if diagonals {
-        vec![
-            TOP_LEFT,
-            TOP,
-            TOP_RIGHT,
-            LEFT,
-            RIGHT,
-            BOTTOM_LEFT,
-            BOTTOM,
-            BOTTOM_RIGHT,
-        ]
-    } else {
-        vec![TOP, LEFT, RIGHT, BOTTOM]
+
+impl From<(Point, Point)> for Rect {
+    fn from((a, b): (Point, Point)) -> Rect {
+        Rect::new(a, b)
+    }
+}
+
+impl From<Rect> for (Point, Point) {
+    fn from(rect: Rect) -> (Point, Point) {
+        (rect.min, rect.max)
+    }
+}
+
+impl IntoIterator for Rect {
+    type Item = Point;
+    type IntoIter = RectPoints;
+
+    fn into_iter(self) -> RectPoints {
+        self.points()
+    }
+}
+
+/// Iterator over the points of a rectangle, see [Rect::points].
+#[derive(Clone, Debug)]
+pub struct RectPoints {
+    rect: Rect,
+    next: Option<Point>,
+}
+
+impl Iterator for RectPoints {
+    type Item = Point;
+
+    fn next(&mut self) -> Option<Point> {
+        let current = self.next?;
+        self.next = if current.x < self.rect.max.x {
+            Some(Point::new(current.x + 1, current.y))
+        } else if current.y < self.rect.max.y {
+            Some(Point::new(self.rect.min.x, current.y + 1))
+        } else {
+            None
+        };
+        Some(current)
     }
-    .iter()
-    .cloned()
-    .map(|o| point + &o)
-    .filter(|p| p.x >= 0 && p.x < width as i32 && p.y >= 0 && p.y < height as i32)
-    .collect()
 }
-impl Add<&Offset> for &Point {
-    type Output = Point;
-    fn add(self, rhs: &Offset) -> Point {
-        Point {
-            x: self.x + rhs.0,
-            y: self.y + rhs.1,
+
+pub const TOP: Offset = Offset::new(0, -1);
+pub const TOP_LEFT: Offset = Offset::new(-1, -1);
+pub const TOP_RIGHT: Offset = Offset::new(1, -1);
+pub const BOTTOM: Offset = Offset::new(0, 1);
+pub const BOTTOM_LEFT: Offset = Offset::new(-1, 1);
+pub const BOTTOM_RIGHT: Offset = Offset::new(1, 1);
+pub const LEFT: Offset = Offset::new(-1, 0);
+pub const RIGHT: Offset = Offset::new(1, 0);
+
+/// Offsets of the 4 orthogonal neighbors, in reading order.
+pub const ORTHOGONAL: [Offset; 4] = [TOP, LEFT, RIGHT, BOTTOM];
+
+/// Offsets of all 8 neighbors, in reading order.
+pub const ALL_AROUND: [Offset; 8] = [
+    TOP_LEFT,
+    TOP,
+    TOP_RIGHT,
+    LEFT,
+    RIGHT,
+    BOTTOM_LEFT,
+    BOTTOM,
+    BOTTOM_RIGHT,
+];
+
+/// Offsets of the 6 neighbors sharing a face.
+pub const ORTHOGONAL_3D: [Point3; 6] = [
+    Point3::new(0, 0, -1),
+    Point3::new(0, -1, 0),
+    Point3::new(-1, 0, 0),
+    Point3::new(1, 0, 0),
+    Point3::new(0, 1, 0),
+    Point3::new(0, 0, 1),
+];
+
+/// Offsets of all 26 neighbors, ordered by z, y and x.
+pub const ALL_AROUND_3D: [Point3; 26] = all_around_3d();
+
+const fn all_around_3d() -> [Point3; 26] {
+    let mut result = [Point3::new(0, 0, 0); 26];
+    let mut i = 0;
+    let mut n = 0;
+    while n < 27 {
+        if n != 13 {
+            result[i] = Point3::new(n % 3 - 1, n / 3 % 3 - 1, n / 9 - 1);
+            i += 1;
         }
+        n += 1;
     }
+    result
+}
+
+/// Which points count as neighbors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Connectivity {
+    /// Points that differ in one coordinate by one: 4 in 2D, 6 in 3D.
+    Orthogonal,
+    /// Points that differ in any coordinates by one: 8 in 2D, 26 in 3D.
+    All,
+}
+
+/// Shape of a 2D grid, which decides the neighbors of its edge points.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Topology {
+    /// An infinite grid.
+    Unbounded,
+    /// A `width` x `height` grid starting at `0,0`: edge points have fewer
+    /// neighbors.
+    Bounded { width: u32, height: u32 },
+    /// A `width` x `height` grid starting at `0,0`, whose opposite edges are
+    /// adjacent. On grids narrower than 3, a point may be listed more than
+    /// once or be its own neighbor.
+    Torus { width: u32, height: u32 },
 }
 
-impl AddAssign<&Offset> for Point {
-    fn add_assign(&mut self, Offset(dx, dy): &Offset) {
-        self.x += dx;
-        self.y += dy;
+/// Iterator over the neighbors of a point, see [Point::neighbors].
+#[derive(Clone, Debug)]
+pub struct Neighbors {
+    center: Point,
+    offsets: std::slice::Iter<'static, Offset>,
+    topology: Topology,
+}
+
+impl Iterator for Neighbors {
+    type Item = Point;
+
+    fn next(&mut self) -> Option<Point> {
+        for offset in self.offsets.by_ref() {
+            let p = self.center + *offset;
+            match self.topology {
+                Topology::Unbounded => return Some(p),
+                Topology::Bounded { width, height } => {
+                    if p.x >= 0 && p.x < width as i32 && p.y >= 0 && p.y < height as i32 {
+                        return Some(p);
+                    }
+                }
+                Topology::Torus { width, height } => {
+                    return Some(Point::new(
+                        p.x.rem_euclid(width as i32),
+                        p.y.rem_euclid(height as i32),
+                    ))
+                }
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.offsets.len()))
     }
 }
 
-impl FromStr for Point {
-    type Err = String;
-    fn from_str(text: &str) -> Result<Point, String> {
-        let (x_str, y_str) = text
-            .split_once(",")
-            .ok_or(format!("Cannot split string {} once by comma", text))?;
+impl Point {
+    /// Neighbors of the point in reading order, without allocating.
+    pub fn neighbors(self, connectivity: Connectivity, topology: Topology) -> Neighbors {
+        let offsets: &'static [Offset] = match connectivity {
+            Connectivity::Orthogonal => &ORTHOGONAL,
+            Connectivity::All => &ALL_AROUND,
+        };
+        Neighbors {
+            center: self,
+            offsets: offsets.iter(),
+            topology,
+        }
+    }
+}
+
+impl Point3 {
+    /// Neighbors of the point on an infinite grid, without allocating.
+    pub fn neighbors(self, connectivity: Connectivity) -> impl Iterator<Item = Point3> {
+        let offsets: &'static [Point3] = match connectivity {
+            Connectivity::Orthogonal => &ORTHOGONAL_3D,
+            Connectivity::All => &ALL_AROUND_3D,
+        };
+        offsets.iter().map(move |o| self + *o)
+    }
+}
+
+/// Parses `x,y`.
+impl<T: FromStr> FromStr for Point<T> {
+    type Err = AocError;
+    fn from_str(text: &str) -> Result<Self, AocError> {
+        let (x_str, y_str) = split_once(text, text, ",")?;
         Ok(Point {
-            x: x_str.parse::<i32>().map_err(|e| e.to_string())?,
-            y: y_str.parse::<i32>().map_err(|e| e.to_string())?,
+            x: parse_token(text, x_str.trim(), "integer")?,
+            y: parse_token(text, y_str.trim(), "integer")?,
         })
     }
 }
+
+/// Parses `x,y,z`.
+impl<T: FromStr> FromStr for Point3<T> {
+    type Err = AocError;
+    fn from_str(text: &str) -> Result<Self, AocError> {
+        let (x_str, rest) = split_once(text, text, ",")?;
+        let (y_str, z_str) = split_once(text, rest, ",")?;
+        Ok(Point3 {
+            x: parse_token(text, x_str.trim(), "integer")?,
+            y: parse_token(text, y_str.trim(), "integer")?,
+            z: parse_token(text, z_str.trim(), "integer")?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ops_test() {
+        let a = Point::new(1, 2);
+        let b = Point::new(4, -2);
+        assert_eq!(a + b, Point::new(5, 0));
+        assert_eq!(a - b, Point::new(-3, 4));
+        assert_eq!(-a, Point::new(-1, -2));
+        assert_eq!(a * 3, Point::new(3, 6));
+        let mut c = a;
+        c += b;
+        c -= a;
+        assert_eq!(c, b);
+        assert_eq!(
+            Point3::new(1, 2, 3) + Point3::new(1, 1, 1) * 2,
+            Point3::new(3, 4, 5)
+        );
+    }
+
+    #[test]
+    fn distance_test() {
+        assert_eq!(Point::new(1, 2).manhattan(Point::new(4, -2)), 7);
+        assert_eq!(Point::new(1, 2).chebyshev(Point::new(4, -2)), 4);
+        assert_eq!(Point::<u32>::new(1, 5).manhattan(Point::new(3, 2)), 5);
+        assert_eq!(
+            Point3::new(1105, -1205, 1229).manhattan(Point3::new(-92, -2380, -20)),
+            3621
+        );
+    }
+
+    #[test]
+    fn convert_test() {
+        let p = Point::<u8>::new(1, 200);
+        assert_eq!(p.convert::<i32>(), Point::new(1, 200));
+        assert_eq!(Point::new(1, -1).try_convert::<u32>(), None);
+        assert_eq!(
+            Point::new(1, 2).try_convert::<u32>(),
+            Some(Point::new(1, 2))
+        );
+        assert_eq!(Point::from((3, 4)), Point::new(3, 4));
+        assert_eq!(<(i32, i32, i32)>::from(Point3::new(1, 2, 3)), (1, 2, 3));
+    }
+
+    #[test]
+    fn rect_test() {
+        let rect = Rect::new(Point::new(3, -1), Point::new(1, 2));
+        assert_eq!(rect.min, Point::new(1, -1));
+        assert_eq!(rect.max, Point::new(3, 2));
+        assert_eq!((rect.width(), rect.height(), rect.area()), (3, 4, 12));
+        assert_eq!(rect.top_left(Orientation::YDown), Point::new(1, -1));
+        assert_eq!(rect.top_left(Orientation::YUp), Point::new(1, 2));
+        assert_eq!(rect.bottom_right(Orientation::YUp), Point::new(3, -1));
+        assert!(rect.contains(&Point::new(3, 2)));
+        assert!(!rect.contains(&Point::new(0, 0)));
+
+        let other = Rect::new(Point::new(3, 2), Point::new(5, 5));
+        assert_eq!(
+            rect.intersection(&other),
+            Some(Rect::new(Point::new(3, 2), Point::new(3, 2)))
+        );
+        assert_eq!(rect.intersection(&rect.expand(1)), Some(rect));
+        assert_eq!(
+            rect.intersection(&Rect::new(Point::new(4, 0), Point::new(5, 0))),
+            None
+        );
+        assert_eq!(
+            rect.union(&other),
+            Rect::new(Point::new(1, -1), Point::new(5, 5))
+        );
+        assert_eq!(
+            rect.expand(1),
+            Rect::new(Point::new(0, -2), Point::new(4, 3))
+        );
+    }
+
+    #[test]
+    fn rect_points_test() {
+        let rect = Rect::new(Point::new(0, 0), Point::new(1, 1));
+        assert_eq!(
+            rect.points().collect::<Vec<_>>(),
+            vec![
+                Point::new(0, 0),
+                Point::new(1, 0),
+                Point::new(0, 1),
+                Point::new(1, 1)
+            ]
+        );
+        let points = [Point::new(2, 5), Point::new(-1, 3), Point::new(0, 7)];
+        let bounds = Rect::from_points(points).unwrap();
+        assert_eq!(bounds, Rect::new(Point::new(-1, 3), Point::new(2, 7)));
+        assert_eq!(bounds.into_iter().count() as u64, bounds.area());
+        assert_eq!(Rect::from_points([]), None);
+        assert_eq!(<(Point, Point)>::from(bounds), (bounds.min, bounds.max));
+    }
+
+    #[test]
+    fn neighbors_test() {
+        let bounded = Topology::Bounded {
+            width: 10,
+            height: 5,
+        };
+        assert_eq!(
+            Point::new(0, 0)
+                .neighbors(Connectivity::Orthogonal, bounded)
+                .collect::<Vec<_>>(),
+            vec![Point::new(1, 0), Point::new(0, 1)]
+        );
+        assert_eq!(
+            Point::new(9, 4)
+                .neighbors(Connectivity::All, bounded)
+                .count(),
+            3
+        );
+        assert_eq!(
+            Point::new(0, 0)
+                .neighbors(Connectivity::All, Topology::Unbounded)
+                .collect::<Vec<_>>(),
+            ALL_AROUND.to_vec()
+        );
+        let torus = Topology::Torus {
+            width: 10,
+            height: 5,
+        };
+        assert_eq!(
+            Point::new(0, 4)
+                .neighbors(Connectivity::Orthogonal, torus)
+                .collect::<Vec<_>>(),
+            vec![
+                Point::new(0, 3),
+                Point::new(9, 4),
+                Point::new(1, 4),
+                Point::new(0, 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn neighbors_3d_test() {
+        let center = Point3::new(1, 2, 3);
+        assert_eq!(center.neighbors(Connectivity::Orthogonal).count(), 6);
+        let all: Vec<Point3> = center.neighbors(Connectivity::All).collect();
+        assert_eq!(all.len(), 26);
+        assert!(all.iter().all(|p| p.chebyshev(center) == 1));
+        assert_eq!(all[0], Point3::new(0, 1, 2));
+        assert!(center
+            .neighbors(Connectivity::Orthogonal)
+            .all(|p| p.manhattan(center) == 1));
+    }
+
+    #[test]
+    fn parse_test() {
+        assert_eq!("3,-4".parse::<Point>().unwrap(), Point::new(3, -4));
+        assert_eq!(
+            "1,2,3".parse::<Point3<i64>>().unwrap(),
+            Point3::new(1i64, 2, 3)
+        );
+        assert_eq!(Point3::new(1, 2, 3).to_string(), "1,2,3");
+        assert_eq!(
+            "1,x".parse::<Point>().unwrap_err().to_string(),
+            "line 1 col 3: expected integer, found \"x\""
+        );
+        assert!("1,2".parse::<Point3>().is_err());
+    }
+}
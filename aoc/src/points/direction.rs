@@ -0,0 +1,208 @@
+//! Compass directions on a grid with y growing down, as in text, and a
+//! turtle that moves along them.
+use super::{Offset, Point};
+use super::{BOTTOM, BOTTOM_LEFT, BOTTOM_RIGHT, LEFT, RIGHT, TOP, TOP_LEFT, TOP_RIGHT};
+use crate::error::AocError;
+use std::str::FromStr;
+
+/// One of the 8 directions to a neighbor, in clockwise order from up.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction {
+    /// The 4 orthogonal directions, clockwise from up.
+    pub const ORTHOGONAL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// All 8 directions, clockwise from up.
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::UpRight,
+        Direction::Right,
+        Direction::DownRight,
+        Direction::Down,
+        Direction::DownLeft,
+        Direction::Left,
+        Direction::UpLeft,
+    ];
+
+    pub fn is_diagonal(self) -> bool {
+        self as u8 % 2 == 1
+    }
+
+    /// Direction turned clockwise by `eighths` of a full turn, counterclockwise
+    /// if negative.
+    pub fn rotate(self, eighths: i32) -> Direction {
+        Direction::ALL[(self as i32 + eighths).rem_euclid(8) as usize]
+    }
+
+    /// Direction a quarter turn clockwise.
+    pub fn turn_right(self) -> Direction {
+        self.rotate(2)
+    }
+
+    /// Direction a quarter turn counterclockwise.
+    pub fn turn_left(self) -> Direction {
+        self.rotate(-2)
+    }
+
+    pub fn reverse(self) -> Direction {
+        self.rotate(4)
+    }
+
+    /// Offset to the neighbor in this direction, up is negative y.
+    pub fn offset(self) -> Offset {
+        match self {
+            Direction::Up => TOP,
+            Direction::UpRight => TOP_RIGHT,
+            Direction::Right => RIGHT,
+            Direction::DownRight => BOTTOM_RIGHT,
+            Direction::Down => BOTTOM,
+            Direction::DownLeft => BOTTOM_LEFT,
+            Direction::Left => LEFT,
+            Direction::UpLeft => TOP_LEFT,
+        }
+    }
+}
+
+impl From<Direction> for Offset {
+    fn from(direction: Direction) -> Offset {
+        direction.offset()
+    }
+}
+
+/// Parses `U`, `D`, `L`, `R`, compass points like `N` or `SE`, or words like
+/// `up` or `north`, in any case.
+impl FromStr for Direction {
+    type Err = AocError;
+
+    fn from_str(text: &str) -> Result<Direction, AocError> {
+        match text.to_ascii_lowercase().as_str() {
+            "u" | "n" | "up" | "north" => Ok(Direction::Up),
+            "ne" | "northeast" => Ok(Direction::UpRight),
+            "r" | "e" | "right" | "east" => Ok(Direction::Right),
+            "se" | "southeast" => Ok(Direction::DownRight),
+            "d" | "s" | "down" | "south" => Ok(Direction::Down),
+            "sw" | "southwest" => Ok(Direction::DownLeft),
+            "l" | "w" | "left" | "west" => Ok(Direction::Left),
+            "nw" | "northwest" => Ok(Direction::UpLeft),
+            _ => Err(AocError::parse(text, text, "expected direction")),
+        }
+    }
+}
+
+/// A position and a heading. The heading is a vector, which starts as a
+/// [Direction] but can be steered to any offset.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub struct Turtle {
+    pub position: Point,
+    pub heading: Offset,
+}
+
+impl Turtle {
+    pub fn new(position: Point, heading: Direction) -> Turtle {
+        Turtle {
+            position,
+            heading: heading.offset(),
+        }
+    }
+
+    /// Moves `distance` times the heading.
+    pub fn forward(&mut self, distance: i32) {
+        self.position += self.heading * distance;
+    }
+
+    /// Moves `distance` steps in `direction` without changing the heading.
+    pub fn step(&mut self, direction: Direction, distance: i32) {
+        self.position += direction.offset() * distance;
+    }
+
+    /// Adds `offset` to the heading.
+    pub fn steer(&mut self, offset: Offset) {
+        self.heading += offset;
+    }
+
+    /// Turns the heading a quarter turn clockwise.
+    pub fn turn_right(&mut self) {
+        self.heading = Offset::new(-self.heading.y, self.heading.x);
+    }
+
+    /// Turns the heading a quarter turn counterclockwise.
+    pub fn turn_left(&mut self) {
+        self.heading = Offset::new(self.heading.y, -self.heading.x);
+    }
+
+    pub fn reverse(&mut self) {
+        self.heading = -self.heading;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turn_test() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::UpLeft.reverse(), Direction::DownRight);
+        assert_eq!(Direction::Left.rotate(3), Direction::UpRight);
+        assert_eq!(Direction::Up.rotate(-9), Direction::UpLeft);
+        assert!(Direction::ORTHOGONAL.iter().all(|d| !d.is_diagonal()));
+        for direction in Direction::ALL {
+            assert_eq!(direction.reverse().offset(), -direction.offset());
+            assert_eq!(
+                direction.offset().chebyshev(Offset::default()),
+                1,
+                "{:?}",
+                direction
+            );
+        }
+        assert_eq!(Offset::from(Direction::DownLeft), Offset::new(-1, 1));
+    }
+
+    #[test]
+    fn parse_test() {
+        for text in ["U", "n", "up", "North"] {
+            assert_eq!(text.parse::<Direction>().unwrap(), Direction::Up);
+        }
+        assert_eq!("SW".parse::<Direction>().unwrap(), Direction::DownLeft);
+        assert_eq!("west".parse::<Direction>().unwrap(), Direction::Left);
+        assert_eq!(
+            "forward".parse::<Direction>().unwrap_err().to_string(),
+            "line 1 col 1: expected direction, found \"forward\""
+        );
+    }
+
+    #[test]
+    fn turtle_test() {
+        let mut turtle = Turtle::new(Point::default(), Direction::Right);
+        turtle.forward(3);
+        turtle.turn_right();
+        turtle.forward(2);
+        assert_eq!(turtle.position, Point::new(3, 2));
+        assert_eq!(turtle.heading, Direction::Down.offset());
+        turtle.turn_left();
+        turtle.turn_left();
+        turtle.reverse();
+        assert_eq!(turtle.heading, Direction::Down.offset());
+        turtle.step(Direction::UpLeft, 2);
+        assert_eq!(turtle.position, Point::new(1, 0));
+        turtle.steer(Offset::new(1, 0));
+        turtle.forward(2);
+        assert_eq!(turtle.position, Point::new(3, 2));
+    }
+}
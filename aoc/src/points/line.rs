@@ -0,0 +1,268 @@
+//! Line segments between integer points: the grid points they cover and
+//! whether they intersect.
+use super::{Offset, Point};
+use crate::error::AocError;
+use crate::parse::split_once;
+use std::fmt;
+use std::str::FromStr;
+
+/// Segment from one point to another, both inclusive.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub struct Segment {
+    pub from: Point,
+    pub to: Point,
+}
+
+/// How a segment is turned into grid points.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub enum Raster {
+    /// Bresenham's line: one point per step along the longer axis, the
+    /// closest to the exact line.
+    Bresenham,
+    /// Only the points exactly on the line.
+    Lattice,
+}
+
+impl Segment {
+    pub const fn new(from: Point, to: Point) -> Segment {
+        Segment { from, to }
+    }
+
+    pub fn is_horizontal(&self) -> bool {
+        self.from.y == self.to.y
+    }
+
+    pub fn is_vertical(&self) -> bool {
+        self.from.x == self.to.x
+    }
+
+    /// Whether the segment is horizontal, vertical or at 45 degrees, where
+    /// both [Raster]s give the same points.
+    pub fn is_octilinear(&self) -> bool {
+        let d = self.to - self.from;
+        d.x == 0 || d.y == 0 || d.x.abs() == d.y.abs()
+    }
+
+    /// Points of the segment from `from` to `to`.
+    pub fn points(&self, raster: Raster) -> SegmentPoints {
+        let dx = (self.to.x as i64 - self.from.x as i64).abs();
+        let dy = (self.to.y as i64 - self.from.y as i64).abs();
+        let sign = Offset::new(
+            (self.to.x - self.from.x).signum(),
+            (self.to.y - self.from.y).signum(),
+        );
+        let (step, remaining) = match raster {
+            Raster::Bresenham => (
+                Step::Bresenham {
+                    dx,
+                    dy: -dy,
+                    sign,
+                    error: dx - dy,
+                },
+                dx.max(dy) + 1,
+            ),
+            Raster::Lattice => {
+                let steps = gcd(dx, dy);
+                let step = if steps == 0 {
+                    Offset::default()
+                } else {
+                    Offset::new(
+                        (self.to.x - self.from.x) / steps as i32,
+                        (self.to.y - self.from.y) / steps as i32,
+                    )
+                };
+                (Step::Lattice(step), steps + 1)
+            }
+        };
+        SegmentPoints {
+            next: self.from,
+            remaining: remaining as u64,
+            step,
+        }
+    }
+
+    /// Whether `point` lies exactly on the segment.
+    pub fn contains(&self, point: &Point) -> bool {
+        cross(self.from, self.to, *point) == 0
+            && point.x >= self.from.x.min(self.to.x)
+            && point.x <= self.from.x.max(self.to.x)
+            && point.y >= self.from.y.min(self.to.y)
+            && point.y <= self.from.y.max(self.to.y)
+    }
+
+    /// Whether the segments share a point, which need not be a grid point.
+    pub fn intersects(&self, other: &Segment) -> bool {
+        let (a, b) = (self.from, self.to);
+        let (c, d) = (other.from, other.to);
+        let (abc, abd) = (cross(a, b, c), cross(a, b, d));
+        let (cda, cdb) = (cross(c, d, a), cross(c, d, b));
+        if abc.signum() * abd.signum() < 0 && cda.signum() * cdb.signum() < 0 {
+            return true;
+        }
+        // Touching or collinear segments share an end point.
+        self.contains(&c) || self.contains(&d) || other.contains(&a) || other.contains(&b)
+    }
+}
+
+/// Cross product of `b - a` and `c - a`: positive if `c` is to the left of
+/// `a -> b` in y-up coordinates, zero if the three points are collinear.
+fn cross(a: Point, b: Point, c: Point) -> i64 {
+    let (abx, aby) = (b.x as i64 - a.x as i64, b.y as i64 - a.y as i64);
+    let (acx, acy) = (c.x as i64 - a.x as i64, c.y as i64 - a.y as i64);
+    abx * acy - aby * acx
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Step {
+    Bresenham {
+        dx: i64,
+        dy: i64,
+        sign: Offset,
+        error: i64,
+    },
+    Lattice(Offset),
+}
+
+/// Iterator over the points of a segment, see [Segment::points].
+#[derive(Clone, Debug)]
+pub struct SegmentPoints {
+    next: Point,
+    remaining: u64,
+    step: Step,
+}
+
+impl Iterator for SegmentPoints {
+    type Item = Point;
+
+    fn next(&mut self) -> Option<Point> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let current = self.next;
+        if self.remaining > 0 {
+            match &mut self.step {
+                Step::Bresenham {
+                    dx,
+                    dy,
+                    sign,
+                    error,
+                } => {
+                    let doubled = 2 * *error;
+                    if doubled >= *dy {
+                        *error += *dy;
+                        self.next.x += sign.x;
+                    }
+                    if doubled <= *dx {
+                        *error += *dx;
+                        self.next.y += sign.y;
+                    }
+                }
+                Step::Lattice(step) => self.next += *step,
+            }
+        }
+        Some(current)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.remaining as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for SegmentPoints {}
+
+/// Parses `x1,y1 -> x2,y2`.
+impl FromStr for Segment {
+    type Err = AocError;
+
+    fn from_str(text: &str) -> Result<Segment, AocError> {
+        let (from, to) = split_once(text, text, " -> ")?;
+        Ok(Segment {
+            from: from.parse().map_err(|e: AocError| e.within(text, from))?,
+            to: to.parse().map_err(|e: AocError| e.within(text, to))?,
+        })
+    }
+}
+
+impl fmt::Display for Segment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} -> {}", self.from, self.to)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(text: &str) -> Segment {
+        text.parse().unwrap()
+    }
+
+    fn points(text: &str, raster: Raster) -> Vec<(i32, i32)> {
+        segment(text).points(raster).map(Into::into).collect()
+    }
+
+    #[test]
+    fn octilinear_test() {
+        for text in [
+            "1,1 -> 1,3",
+            "9,7 -> 7,7",
+            "1,1 -> 3,3",
+            "9,7 -> 7,9",
+            "2,2 -> 2,2",
+        ] {
+            assert!(segment(text).is_octilinear());
+            assert_eq!(
+                points(text, Raster::Bresenham),
+                points(text, Raster::Lattice)
+            );
+        }
+        assert_eq!(
+            points("9,7 -> 7,9", Raster::Lattice),
+            vec![(9, 7), (8, 8), (7, 9)]
+        );
+        assert_eq!(points("2,2 -> 2,2", Raster::Lattice), vec![(2, 2)]);
+    }
+
+    #[test]
+    fn slope_test() {
+        assert!(!segment("0,0 -> 6,3").is_octilinear());
+        assert_eq!(
+            points("0,0 -> 6,3", Raster::Lattice),
+            vec![(0, 0), (2, 1), (4, 2), (6, 3)]
+        );
+        assert_eq!(
+            points("0,0 -> 6,3", Raster::Bresenham),
+            vec![(0, 0), (1, 1), (2, 1), (3, 2), (4, 2), (5, 3), (6, 3)]
+        );
+        assert_eq!(
+            points("3,6 -> 0,0", Raster::Bresenham),
+            vec![(3, 6), (2, 5), (2, 4), (1, 3), (1, 2), (0, 1), (0, 0)]
+        );
+        assert_eq!(points("0,0 -> 3,2", Raster::Lattice), vec![(0, 0), (3, 2)]);
+        assert_eq!(segment("0,0 -> 3,2").points(Raster::Bresenham).len(), 4);
+    }
+
+    #[test]
+    fn intersects_test() {
+        let a = segment("0,0 -> 4,4");
+        assert!(a.intersects(&segment("0,4 -> 4,0")));
+        assert!(a.intersects(&segment("0,1 -> 1,0")));
+        assert!(a.intersects(&segment("4,4 -> 9,0")));
+        assert!(a.intersects(&segment("3,3 -> 7,7")));
+        assert!(!a.intersects(&segment("5,5 -> 7,7")));
+        assert!(!a.intersects(&segment("1,0 -> 4,3")));
+        assert!(a.contains(&Point::new(2, 2)));
+        assert!(!a.contains(&Point::new(2, 3)));
+        assert_eq!(a.to_string(), "0,0 -> 4,4");
+    }
+}
@@ -0,0 +1,126 @@
+//! The 8 symmetries of a square: rotations by quarter turns, optionally
+//! after a mirror flip. Rotations are clockwise with y growing down.
+use super::{Point, Rect};
+
+/// Flips `x` if `flipped`, then rotates clockwise by `quarter_turns`.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub struct Symmetry {
+    pub flipped: bool,
+    pub quarter_turns: u8,
+}
+
+impl Symmetry {
+    pub const IDENTITY: Symmetry = Symmetry::new(false, 0);
+    pub const ROTATE_90: Symmetry = Symmetry::new(false, 1);
+    pub const ROTATE_180: Symmetry = Symmetry::new(false, 2);
+    pub const ROTATE_270: Symmetry = Symmetry::new(false, 3);
+    /// Mirrors left and right.
+    pub const FLIP_HORIZONTAL: Symmetry = Symmetry::new(true, 0);
+    /// Mirrors top and bottom.
+    pub const FLIP_VERTICAL: Symmetry = Symmetry::new(true, 2);
+    /// Swaps x and y.
+    pub const TRANSPOSE: Symmetry = Symmetry::new(true, 3);
+    /// Swaps x and y and mirrors both.
+    pub const ANTI_TRANSPOSE: Symmetry = Symmetry::new(true, 1);
+
+    /// All 8 symmetries, starting with the identity.
+    pub const ALL: [Symmetry; 8] = [
+        Symmetry::IDENTITY,
+        Symmetry::ROTATE_90,
+        Symmetry::ROTATE_180,
+        Symmetry::ROTATE_270,
+        Symmetry::FLIP_HORIZONTAL,
+        Symmetry::ANTI_TRANSPOSE,
+        Symmetry::FLIP_VERTICAL,
+        Symmetry::TRANSPOSE,
+    ];
+
+    pub const fn new(flipped: bool, quarter_turns: u8) -> Symmetry {
+        Symmetry {
+            flipped,
+            quarter_turns: quarter_turns % 4,
+        }
+    }
+
+    /// Rotation clockwise by `quarter_turns`, counterclockwise if negative.
+    pub fn rotation(quarter_turns: i32) -> Symmetry {
+        Symmetry::new(false, quarter_turns.rem_euclid(4) as u8)
+    }
+
+    /// Whether the symmetry swaps width and height.
+    pub fn swaps_axes(self) -> bool {
+        self.quarter_turns % 2 == 1
+    }
+
+    /// Image of `point` with the origin fixed.
+    pub fn apply(self, point: Point) -> Point {
+        let mut p = if self.flipped {
+            Point::new(-point.x, point.y)
+        } else {
+            point
+        };
+        for _ in 0..self.quarter_turns {
+            p = Point::new(-p.y, p.x);
+        }
+        p
+    }
+
+    /// Image of `rect`, moved back to start at the same top left corner.
+    pub fn apply_to_rect(self, rect: &Rect) -> Rect {
+        let size = rect.max - rect.min;
+        let size = if self.swaps_axes() {
+            Point::new(size.y, size.x)
+        } else {
+            size
+        };
+        Rect {
+            min: rect.min,
+            max: rect.min + size,
+        }
+    }
+
+    /// Image of `point` of `rect` in [apply_to_rect](Symmetry::apply_to_rect).
+    pub fn apply_in(self, rect: &Rect, point: Point) -> Point {
+        let corners = Rect::new(
+            self.apply(Point::default()),
+            self.apply(rect.max - rect.min),
+        );
+        self.apply(point - rect.min) - corners.min + rect.min
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn apply_test() {
+        let p = Point::new(2, 1);
+        assert_eq!(Symmetry::ROTATE_90.apply(p), Point::new(-1, 2));
+        assert_eq!(Symmetry::rotation(-1), Symmetry::ROTATE_270);
+        assert_eq!(Symmetry::FLIP_HORIZONTAL.apply(p), Point::new(-2, 1));
+        assert_eq!(Symmetry::FLIP_VERTICAL.apply(p), Point::new(2, -1));
+        assert_eq!(Symmetry::TRANSPOSE.apply(p), Point::new(1, 2));
+        assert_eq!(Symmetry::ANTI_TRANSPOSE.apply(p), Point::new(-1, -2));
+        let images: HashSet<Point> = Symmetry::ALL.iter().map(|s| s.apply(p)).collect();
+        assert_eq!(images.len(), 8);
+    }
+
+    #[test]
+    fn apply_in_test() {
+        let rect = Rect::new(Point::new(10, 20), Point::new(12, 21));
+        for symmetry in Symmetry::ALL {
+            let image = symmetry.apply_to_rect(&rect);
+            assert_eq!(image.min, rect.min);
+            assert_eq!(image.area(), rect.area());
+            let points: HashSet<Point> =
+                rect.points().map(|p| symmetry.apply_in(&rect, p)).collect();
+            assert_eq!(points, image.points().collect(), "{:?}", symmetry);
+        }
+        assert_eq!(
+            Symmetry::ROTATE_90.apply_in(&rect, Point::new(10, 20)),
+            Point::new(11, 20)
+        );
+    }
+}
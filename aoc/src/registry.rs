@@ -0,0 +1,346 @@
+use super::error::AocError;
+use super::solution::Solution;
+use std::any::Any;
+use std::fmt;
+use std::fmt::Display;
+
+/// A single implementation of a part of a [Solution] with the input and
+/// answer types erased, so that all days can be driven the same way.
+pub trait Part: Sync {
+    fn day(&self) -> u8;
+    fn part(&self) -> u8;
+    /// Name of the implementation, see [Solution::part1_impls].
+    fn name(&self) -> &'static str;
+    /// Name of the type of the answer, without module paths.
+    fn answer_type(&self) -> String;
+    fn parse(&self, text: &str) -> Result<Box<dyn Any>, AocError>;
+
+    /// Solves the part for the input returned by [Part::parse].
+    fn solve(&self, input: &dyn Any) -> String;
+
+    fn run(&self, text: &str) -> Result<String, AocError> {
+        let input = self.parse(text)?;
+        Ok(self.solve(input.as_ref()))
+    }
+}
+
+struct Impl<S: Solution, A> {
+    part: u8,
+    name: &'static str,
+    solve: fn(&S::Input) -> A,
+}
+
+impl<S: Solution, A: Display> Part for Impl<S, A> {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn part(&self) -> u8 {
+        self.part
+    }
+
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn answer_type(&self) -> String {
+        short_type_name(std::any::type_name::<A>())
+    }
+
+    fn parse(&self, text: &str) -> Result<Box<dyn Any>, AocError> {
+        Ok(Box::new(S::parse(text).map_err(|e| e.in_day(S::DAY))?))
+    }
+
+    fn solve(&self, input: &dyn Any) -> String {
+        let input = input
+            .downcast_ref::<S::Input>()
+            .unwrap_or_else(|| panic!("Input of another day passed to day {}", S::DAY));
+        (self.solve)(input).to_string()
+    }
+}
+
+/// Strips module paths from a type name: `Vec<String>` for
+/// `alloc::vec::Vec<alloc::string::String>`.
+fn short_type_name(name: &str) -> String {
+    let mut result = String::new();
+    let mut path = String::new();
+    for ch in name.chars().chain(std::iter::once(' ')) {
+        if ch.is_alphanumeric() || ch == '_' || ch == ':' {
+            path.push(ch);
+        } else {
+            result.push_str(path.rsplit("::").next().unwrap());
+            result.push(ch);
+            path.clear();
+        }
+    }
+    result.pop();
+    result
+}
+
+/// Maps `(day, part)` to its implementations.
+#[derive(Default)]
+pub struct Registry {
+    /// Implementations of every part ordered by day and part, each with the
+    /// default implementation first.
+    parts: Vec<Vec<Box<dyn Part>>>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Registry { parts: Vec::new() }
+    }
+
+    pub fn register<S: Solution + 'static>(&mut self)
+    where
+        S::Answer1: 'static,
+        S::Answer2: 'static,
+    {
+        self.parts.push(
+            S::part1_impls()
+                .into_iter()
+                .map(|(name, solve)| {
+                    Box::new(Impl::<S, _> {
+                        part: 1,
+                        name,
+                        solve,
+                    }) as Box<dyn Part>
+                })
+                .collect(),
+        );
+        self.parts.push(
+            S::part2_impls()
+                .into_iter()
+                .map(|(name, solve)| {
+                    Box::new(Impl::<S, _> {
+                        part: 2,
+                        name,
+                        solve,
+                    }) as Box<dyn Part>
+                })
+                .collect(),
+        );
+        self.parts.retain(|impls| !impls.is_empty());
+        self.parts
+            .sort_by_key(|impls| (impls[0].day(), impls[0].part()));
+    }
+
+    /// Default implementation of a part.
+    pub fn get(&self, day: u8, part: u8) -> Option<&dyn Part> {
+        self.impls(day, part).next()
+    }
+
+    /// Implementation of a part by name.
+    pub fn get_impl(&self, day: u8, part: u8, name: &str) -> Option<&dyn Part> {
+        self.impls(day, part).find(|p| p.name() == name)
+    }
+
+    /// All implementations of a part, the default first.
+    pub fn impls(&self, day: u8, part: u8) -> impl Iterator<Item = &dyn Part> {
+        self.parts
+            .iter()
+            .filter(move |impls| impls[0].day() == day && impls[0].part() == part)
+            .flatten()
+            .map(|p| p.as_ref())
+    }
+
+    /// Default implementations of all registered parts, ordered by day and
+    /// part.
+    pub fn parts(&self) -> impl Iterator<Item = &dyn Part> {
+        self.parts.iter().map(|impls| impls[0].as_ref())
+    }
+
+    pub fn days(&self) -> Vec<u8> {
+        let mut days: Vec<u8> = self.parts().map(|p| p.day()).collect();
+        days.dedup();
+        days
+    }
+}
+
+/// Answers of several implementations of a part on the same input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CrossCheck {
+    pub answers: Vec<(&'static str, String)>,
+}
+
+impl CrossCheck {
+    /// Solves `input`, as returned by [Part::parse], with each of `impls`.
+    pub fn run<'a>(impls: impl IntoIterator<Item = &'a dyn Part>, input: &dyn Any) -> Self {
+        CrossCheck {
+            answers: impls
+                .into_iter()
+                .map(|p| (p.name(), p.solve(input).trim_end().to_string()))
+                .collect(),
+        }
+    }
+
+    pub fn agrees(&self) -> bool {
+        self.answers.windows(2).all(|w| w[0].1 == w[1].1)
+    }
+}
+
+/// Lists the answers as `name=answer`.
+impl fmt::Display for CrossCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (name, answer)) in self.answers.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}={}", name, answer)?;
+        }
+        Ok(())
+    }
+}
+
+/// Registers the days enabled by cargo features.
+fn all_days() -> Registry {
+    #[allow(unused_mut)] // Without any day features.
+    let mut registry = Registry::new();
+    #[cfg(feature = "day1")]
+    registry.register::<crate::day1::Day1>();
+    #[cfg(feature = "day2")]
+    registry.register::<crate::day2::Day2>();
+    #[cfg(feature = "day3")]
+    registry.register::<crate::day3::Day3>();
+    #[cfg(feature = "day4")]
+    registry.register::<crate::day4::Day4>();
+    #[cfg(feature = "day5")]
+    registry.register::<crate::day5::Day5>();
+    #[cfg(feature = "day6")]
+    registry.register::<crate::day6::Day6>();
+    #[cfg(feature = "day7")]
+    registry.register::<crate::day7::Day7>();
+    #[cfg(feature = "day8")]
+    registry.register::<crate::day8::Day8>();
+    #[cfg(feature = "day9")]
+    registry.register::<crate::day9::Day9>();
+    #[cfg(feature = "day10")]
+    registry.register::<crate::day10::Day10>();
+    #[cfg(feature = "day11")]
+    registry.register::<crate::day11::Day11>();
+    #[cfg(feature = "day12")]
+    registry.register::<crate::day12::Day12>();
+    #[cfg(feature = "day13")]
+    registry.register::<crate::day13::Day13>();
+    #[cfg(feature = "day14")]
+    registry.register::<crate::day14::Day14>();
+    #[cfg(feature = "day15")]
+    registry.register::<crate::day15::Day15>();
+    #[cfg(feature = "day16")]
+    registry.register::<crate::day16::Day16>();
+    #[cfg(feature = "day17")]
+    registry.register::<crate::day17::Day17>();
+    #[cfg(feature = "day18")]
+    registry.register::<crate::day18::Day18>();
+    #[cfg(feature = "day19")]
+    registry.register::<crate::day19::Day19>();
+    #[cfg(feature = "day20")]
+    registry.register::<crate::day20::Day20>();
+    #[cfg(feature = "day21")]
+    registry.register::<crate::day21::Day21>();
+    #[cfg(feature = "day22")]
+    registry.register::<crate::day22::Day22>();
+    registry
+}
+
+lazy_static! {
+    static ref REGISTRY: Registry = all_days();
+}
+
+/// Registry with every day of the crate.
+pub fn registry() -> &'static Registry {
+    &REGISTRY
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{Implementation, DEFAULT_IMPL};
+
+    #[test]
+    #[cfg(feature = "day7")]
+    fn lookup_test() {
+        let part = registry().get(7, 2).unwrap();
+        assert_eq!(part.day(), 7);
+        assert_eq!(part.part(), 2);
+        assert!(registry().get(7, 3).is_none());
+        assert!(registry().get(25, 1).is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "day7")]
+    fn run_test() {
+        let part = registry().get(7, 1).unwrap();
+        assert_eq!(part.run("16,1,2,0,4,2,7,1,2,14").unwrap(), "37");
+        assert_eq!(
+            part.run("16,x").unwrap_err().to_string(),
+            "day7 line 1 col 4: invalid digit found in string, found \"x\""
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "all-days")]
+    fn all_days_test() {
+        let part = registry().get(1, 1).unwrap();
+        assert_eq!(
+            part.run("199\n200\n208\n210\n200\n207\n240\n269\n260\n263")
+                .unwrap(),
+            "7"
+        );
+        assert_eq!(registry().days(), (1..=22).collect::<Vec<u8>>());
+    }
+
+    struct Double;
+
+    impl Solution for Double {
+        const DAY: u8 = 30;
+        type Input = u32;
+        type Answer1 = u32;
+        type Answer2 = u32;
+
+        fn parse(text: &str) -> Result<u32, AocError> {
+            Ok(text.trim().parse()?)
+        }
+
+        fn part1(input: &u32) -> u32 {
+            input * 2
+        }
+
+        fn part2(input: &u32) -> u32 {
+            input * 3
+        }
+
+        fn part1_impls() -> Vec<Implementation<u32, u32>> {
+            vec![
+                ("mul", Self::part1),
+                ("add", |x| x + x),
+                ("shift", |x| x << 2),
+            ]
+        }
+    }
+
+    #[test]
+    fn impls_test() {
+        let mut registry = Registry::new();
+        registry.register::<Double>();
+        assert_eq!(registry.parts().count(), 2);
+        assert_eq!(registry.get(30, 1).unwrap().name(), "mul");
+        assert_eq!(registry.get(30, 2).unwrap().name(), DEFAULT_IMPL);
+        assert_eq!(
+            registry.impls(30, 1).map(|p| p.name()).collect::<Vec<_>>(),
+            vec!["mul", "add", "shift"]
+        );
+        assert!(registry.get_impl(30, 2, "add").is_none());
+        assert_eq!(registry.get(30, 1).unwrap().answer_type(), "u32");
+        assert_eq!(
+            short_type_name("alloc::vec::Vec<alloc::string::String>"),
+            "Vec<String>"
+        );
+
+        let input = registry.get(30, 1).unwrap().parse("5").unwrap();
+        let check = CrossCheck::run(registry.impls(30, 1).take(2), input.as_ref());
+        assert!(check.agrees());
+        let check = CrossCheck::run(registry.impls(30, 1), input.as_ref());
+        assert!(!check.agrees());
+        assert_eq!(check.to_string(), "mul=10, add=10, shift=20");
+    }
+}
@@ -0,0 +1,278 @@
+//! Runs of many parts at once, spread over several threads, and their
+//! results as JSON or TSV records.
+use super::answers::Answers;
+use super::error::AocError;
+use super::input::Variant;
+use super::output::{capture, capture_panics, panic_message};
+use super::registry::Part;
+use rayon::prelude::*;
+use serde_json::{json, Value};
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+
+/// Outcome of running one part on its input.
+#[derive(Debug)]
+pub struct PartRun {
+    pub day: u8,
+    pub part: u8,
+    /// Name of the implementation of the part.
+    pub implementation: &'static str,
+    pub answer_type: String,
+    pub answer: Result<String, AocError>,
+    pub parse: Duration,
+    pub solve: Duration,
+    /// Records logged by the part, see [crate::output].
+    pub output: String,
+}
+
+/// Columns of [PartRun::to_tsv].
+pub const TSV_HEADER: &str =
+    "day\tpart\timpl\tinput\tanswer\tanswer_type\tparse_ns\tsolve_ns\tstatus";
+
+impl PartRun {
+    /// Verification status of the answer on `input`: `pass`, `fail` or
+    /// `missing`, or `error` if there is no answer.
+    pub fn status(&self, answers: &Answers, input: &Variant) -> &'static str {
+        match &self.answer {
+            Ok(answer) => answers.check(self.day, self.part, input, answer).name(),
+            Err(_) => "error",
+        }
+    }
+
+    /// Result record of a run on the input named `input`. The answer is
+    /// null and `error` set if the run failed.
+    pub fn to_json(&self, input: &str, status: &str) -> Value {
+        let mut record = json!({
+            "day": self.day,
+            "part": self.part,
+            "impl": self.implementation,
+            "input": input,
+            "answer": self.answer.as_ref().ok().map(|a| a.trim_end()),
+            "answer_type": self.answer_type,
+            "parse_ns": self.parse.as_nanos() as u64,
+            "solve_ns": self.solve.as_nanos() as u64,
+            "status": status,
+        });
+        if let Err(e) = &self.answer {
+            record["error"] = json!(e.to_string());
+        }
+        record
+    }
+
+    /// Result record as a line of tab separated values, see [TSV_HEADER].
+    /// The answer of a failed run is the error.
+    pub fn to_tsv(&self, input: &str, status: &str) -> String {
+        let answer = match &self.answer {
+            Ok(answer) => answer.trim_end().to_string(),
+            Err(e) => e.to_string(),
+        };
+        [
+            self.day.to_string(),
+            self.part.to_string(),
+            self.implementation.to_string(),
+            escape_tsv(input),
+            escape_tsv(&answer),
+            self.answer_type.clone(),
+            self.parse.as_nanos().to_string(),
+            self.solve.as_nanos().to_string(),
+            status.to_string(),
+        ]
+        .join("\t")
+    }
+}
+
+/// Escapes backslashes, tabs and line breaks, so that multi-line answers fit
+/// in one field.
+fn escape_tsv(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+/// Calls `f`, turning a panic into an error of `part`.
+fn catch_panic<T>(part: &dyn Part, f: impl FnOnce() -> T) -> Result<T, AocError> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| AocError::Panic {
+        day: part.day(),
+        part: part.part(),
+        message: panic_message(payload.as_ref()),
+    })
+}
+
+/// Parses `text` and solves `part`, capturing what it logs and its panic
+/// message. A panic fails the run like a parse error does.
+pub fn run_part(part: &dyn Part, text: &str) -> PartRun {
+    let ((answer, parse, solve), output) = capture_panics(|| {
+        capture(|| {
+            let start = Instant::now();
+            let input = match catch_panic(part, || part.parse(text)).and_then(|input| input) {
+                Ok(input) => input,
+                Err(e) => return (Err(e), start.elapsed(), Duration::ZERO),
+            };
+            let parse = start.elapsed();
+            let start = Instant::now();
+            let answer = catch_panic(part, || part.solve(input.as_ref()));
+            (answer, parse, start.elapsed())
+        })
+    });
+    PartRun {
+        day: part.day(),
+        part: part.part(),
+        implementation: part.name(),
+        answer_type: part.answer_type(),
+        answer,
+        parse,
+        solve,
+        output,
+    }
+}
+
+/// Runs each of `parts` on the input `load` returns for its day, using
+/// `jobs` threads, or one per CPU if `jobs` is 0. Runs are returned in the
+/// order of `parts`, those that could not load their input with the error.
+pub fn run_parallel(
+    parts: &[&dyn Part],
+    load: impl Fn(u8) -> Result<String, AocError> + Sync,
+    jobs: usize,
+) -> Vec<PartRun> {
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(jobs)
+        .build()
+        .expect("Cannot start worker threads");
+    pool.install(|| {
+        parts
+            .par_iter()
+            .map(|part| match load(part.day()) {
+                Ok(text) => run_part(*part, &text),
+                Err(e) => PartRun {
+                    day: part.day(),
+                    part: part.part(),
+                    implementation: part.name(),
+                    answer_type: part.answer_type(),
+                    answer: Err(e),
+                    parse: Duration::ZERO,
+                    solve: Duration::ZERO,
+                    output: String::new(),
+                },
+            })
+            .collect()
+    })
+}
+
+#[cfg(all(test, feature = "day4", feature = "day7"))]
+mod tests {
+    use super::*;
+    use crate::input::bundled;
+    use crate::registry::registry;
+    use std::any::Any;
+
+    #[test]
+    fn run_parallel_test() {
+        crate::output::init("day4=debug".parse().unwrap());
+        let parts: Vec<&dyn Part> = registry()
+            .parts()
+            .filter(|p| [4, 7].contains(&p.day()))
+            .collect();
+        let runs = run_parallel(
+            &parts,
+            |day| match day {
+                4 => Ok(bundled(4, "sample")),
+                _ => Err(AocError::NoSolution { day, part: None }),
+            },
+            4,
+        );
+        assert_eq!(
+            runs.iter().map(|r| (r.day, r.part)).collect::<Vec<_>>(),
+            vec![(4, 1), (4, 2), (7, 1), (7, 2)]
+        );
+        assert_eq!(runs[0].answer.as_deref(), Ok("4512"));
+        assert_eq!(runs[0].output, "DEBUG day4: sum: 188, number: 24\n");
+        assert_eq!(runs[2].output, "");
+        assert!(runs[3].answer.is_err());
+
+        let mut answers = Answers::new();
+        answers.record(4, 1, &Variant::sample(), "4512");
+        assert_eq!(runs[0].status(&answers, &Variant::sample()), "pass");
+        assert_eq!(runs[1].status(&answers, &Variant::sample()), "missing");
+        assert_eq!(runs[3].status(&answers, &Variant::sample()), "error");
+
+        let record = runs[0].to_json("sample", "pass");
+        assert_eq!(record["answer"], "4512");
+        assert_eq!(record["answer_type"], "usize");
+        assert_eq!(record["impl"], "default");
+        assert!(record["parse_ns"].is_u64());
+        assert!(record.get("error").is_none());
+        let record = runs[3].to_json("sample", "error");
+        assert!(record["answer"].is_null());
+        assert_eq!(record["error"], "no solution for day 7");
+
+        let tsv = runs[0].to_tsv("sample", "pass");
+        let fields: Vec<&str> = tsv.split('\t').collect();
+        assert_eq!(fields.len(), TSV_HEADER.split('\t').count());
+        assert_eq!(
+            fields[..6],
+            ["4", "1", "default", "sample", "4512", "usize"]
+        );
+        assert_eq!(escape_tsv("#.\n.#\t\\"), "#.\\n.#\\t\\\\");
+    }
+
+    /// Part that logs and then panics when solving.
+    struct Panicking;
+
+    impl Part for Panicking {
+        fn day(&self) -> u8 {
+            4
+        }
+
+        fn part(&self) -> u8 {
+            2
+        }
+
+        fn name(&self) -> &'static str {
+            "panicking"
+        }
+
+        fn answer_type(&self) -> String {
+            "usize".to_string()
+        }
+
+        fn parse(&self, text: &str) -> Result<Box<dyn Any>, AocError> {
+            Ok(Box::new(text.to_string()))
+        }
+
+        fn solve(&self, _: &dyn Any) -> String {
+            log::warn!(target: "aoc::day4", "about to fail");
+            panic!("no board wins")
+        }
+    }
+
+    #[test]
+    fn panic_test() {
+        crate::output::init("day4=debug".parse().unwrap());
+        let day4 = registry().parts().find(|p| p.day() == 4).unwrap();
+        let parts: Vec<&dyn Part> = vec![&Panicking, day4];
+        let runs = run_parallel(&parts, |_| Ok(bundled(4, "sample")), 2);
+        assert_eq!(
+            runs[0].answer,
+            Err(AocError::Panic {
+                day: 4,
+                part: 2,
+                message: "no board wins".to_string()
+            })
+        );
+        let (log, panic) = runs[0].output.split_once('\n').unwrap();
+        assert_eq!(log, "WARN day4: about to fail");
+        assert!(
+            panic.starts_with("panicked at aoc/src/runner.rs:"),
+            "{}",
+            panic
+        );
+        assert!(panic.ends_with(": no board wins\n"), "{}", panic);
+        assert_eq!(runs[0].status(&Answers::new(), &Variant::sample()), "error");
+        assert_eq!(
+            runs[0].to_json("sample", "error")["error"],
+            "day 4 part 2 panicked: no board wins"
+        );
+        assert_eq!(runs[1].answer.as_deref(), Ok("4512"));
+    }
+}
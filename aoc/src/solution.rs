@@ -0,0 +1,34 @@
+use super::error::AocError;
+use std::fmt::Display;
+
+/// A solution for a single day: parses the puzzle input once and solves
+/// both parts on the parsed value.
+pub trait Solution {
+    /// Day of the advent calendar this solution is for.
+    const DAY: u8;
+
+    type Input: 'static;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(text: &str) -> Result<Self::Input, AocError>;
+    fn part1(input: &Self::Input) -> Self::Answer1;
+    fn part2(input: &Self::Input) -> Self::Answer2;
+
+    /// Named implementations of part 1, the first one is the default. Days
+    /// with a single implementation only have [Solution::part1].
+    fn part1_impls() -> Vec<Implementation<Self::Input, Self::Answer1>> {
+        vec![(DEFAULT_IMPL, Self::part1)]
+    }
+
+    /// Named implementations of part 2, the first one is the default.
+    fn part2_impls() -> Vec<Implementation<Self::Input, Self::Answer2>> {
+        vec![(DEFAULT_IMPL, Self::part2)]
+    }
+}
+
+/// Name of the only implementation of a part.
+pub const DEFAULT_IMPL: &str = "default";
+
+/// A named implementation of a part.
+pub type Implementation<I, A> = (&'static str, fn(&I) -> A);
@@ -1,97 +1,564 @@
+use super::error::AocError;
+use super::parse::parse_line_separated;
+use super::points::symmetry::Symmetry;
 use super::points::*;
-use std::collections::HashSet;
+use super::vec_field::VecField;
+use std::cell::Cell;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
+use std::ops::{BitAnd, BitOr, BitXor, Sub};
 
-#[derive(Clone)]
+/// Set of points on an infinite grid.
+#[derive(Clone, Debug, Default)]
 pub struct SparseBoolField {
-    pub points: HashSet<Point>,
+    points: HashSet<Point>,
+    bounds: BoundsTracker,
 }
 
 impl SparseBoolField {
     pub fn new() -> SparseBoolField {
-        SparseBoolField {
-            points: HashSet::new(),
-        }
+        SparseBoolField::default()
     }
-    pub fn from_map(text: &str, true_ch: char) -> SparseBoolField{
-        let mut x: i32;
-        let mut y = 0;
-        let mut result= SparseBoolField::new();
-        for line in text.trim().lines() {
-            x = 0;
-            for ch in line.chars() {
+    pub fn from_map(text: &str, true_ch: char) -> SparseBoolField {
+        let mut result = SparseBoolField::new();
+        for (y, line) in text.trim().lines().enumerate() {
+            for (x, ch) in line.chars().enumerate() {
                 if ch == true_ch {
-                    result.set(&Point { x, y });
+                    result.set(&Point {
+                        x: x as i32,
+                        y: y as i32,
+                    });
                 }
-                x += 1;
             }
-            y += 1;
         }
         result
-        
     }
-    pub fn from_coords(text: &str) -> Result<SparseBoolField, String> {
-        Ok(text
-            .lines()
-            .map(|line| line.parse::<Point>())
-            .collect::<Result<Vec<Point>, String>>()?
-            .into_iter()
-            .fold(SparseBoolField::new(), |mut field, point| {
-                field.set(&point);
-                field
-            }))
+    pub fn from_coords(text: &str) -> Result<SparseBoolField, AocError> {
+        Ok(parse_line_separated::<Point>(text)?.into_iter().collect())
     }
 
     pub fn get(&self, point: &Point) -> bool {
         self.points.contains(point)
     }
 
+    /// Sets `point`, returns whether it was not set before.
     pub fn set(&mut self, point: &Point) -> bool {
+        self.bounds.add(*point);
         self.points.insert(*point)
     }
 
+    /// Clears `point`, returns whether it was set before.
     pub fn clear(&mut self, point: &Point) -> bool {
-        self.points.remove(point)
-    }
-
-    pub fn bounds(&self) -> (Point, Point) {
-        self.points.iter().fold(
-            (
-                Point {
-                    x: i32::MAX,
-                    y: i32::MAX,
-                },
-                Point {
-                    x: i32::MIN,
-                    y: i32::MIN,
-                },
-            ),
-            |(top_left, bottom_right), point| {
-                (
-                    Point {
-                        x: top_left.x.min(point.x),
-                        y: top_left.y.min(point.y),
-                    },
-                    Point {
-                        x: bottom_right.x.max(point.x),
-                        y: bottom_right.y.max(point.y),
-                    },
-                )
-            },
-        )
+        let removed = self.points.remove(point);
+        if removed {
+            self.bounds.remove(*point);
+        }
+        removed
+    }
+
+    /// Number of set points.
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Set points, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = Point> + '_ {
+        self.points.iter().copied()
+    }
+
+    /// Smallest rectangle containing all set points, `None` if there are
+    /// none. Takes constant time unless points on the edge of the bounds
+    /// were cleared since the last call.
+    pub fn bounds(&self) -> Option<Rect> {
+        self.bounds.get(|| self.points.iter().copied())
+    }
+
+    /// Field with each point moved by `symmetry` inside the bounds, see
+    /// [Symmetry::apply_to_rect].
+    pub fn transform(&self, symmetry: Symmetry) -> SparseBoolField {
+        let bounds = match self.bounds() {
+            Some(bounds) => bounds,
+            None => return self.clone(),
+        };
+        self.iter().map(|p| symmetry.apply_in(&bounds, p)).collect()
+    }
+
+    /// Field rotated clockwise by `quarter_turns`, counterclockwise if
+    /// negative.
+    pub fn rotate(&self, quarter_turns: i32) -> SparseBoolField {
+        self.transform(Symmetry::rotation(quarter_turns))
+    }
+
+    /// Field with left and right swapped.
+    pub fn flip_horizontal(&self) -> SparseBoolField {
+        self.transform(Symmetry::FLIP_HORIZONTAL)
+    }
+
+    /// Field with top and bottom swapped.
+    pub fn flip_vertical(&self) -> SparseBoolField {
+        self.transform(Symmetry::FLIP_VERTICAL)
+    }
+
+    pub fn transpose(&self) -> SparseBoolField {
+        self.transform(Symmetry::TRANSPOSE)
+    }
+
+    /// The field in each of [Symmetry::ALL].
+    pub fn symmetries(&self) -> impl Iterator<Item = SparseBoolField> + '_ {
+        Symmetry::ALL.into_iter().map(|s| self.transform(s))
+    }
+
+    /// Set points inside `rect`, see [Rect].
+    pub fn crop(&self, rect: &Rect) -> SparseBoolField {
+        self.iter().filter(|p| rect.contains(p)).collect()
+    }
+
+    /// Field with `margin` points around its bounds set to `fill`.
+    pub fn pad(&self, margin: u32, fill: bool) -> SparseBoolField {
+        let mut result = self.clone();
+        if let (Some(bounds), true) = (self.bounds(), fill) {
+            for point in bounds.expand(margin).points() {
+                if !bounds.contains(&point) {
+                    result.set(&point);
+                }
+            }
+        }
+        result
+    }
+
+    /// Set points matching `predicate`.
+    pub fn filter(&self, predicate: impl Fn(&Point) -> bool) -> SparseBoolField {
+        self.iter().filter(predicate).collect()
+    }
+
+    /// Points set in either field.
+    pub fn union(&self, other: &SparseBoolField) -> SparseBoolField {
+        let mut result = self.clone();
+        result.extend(other.iter());
+        result
+    }
+
+    /// Points set in both fields.
+    pub fn intersection(&self, other: &SparseBoolField) -> SparseBoolField {
+        self.filter(|p| other.get(p))
+    }
+
+    /// Points set in this field but not in `other`.
+    pub fn difference(&self, other: &SparseBoolField) -> SparseBoolField {
+        self.filter(|p| !other.get(p))
+    }
+
+    /// Points set in exactly one of the fields.
+    pub fn symmetric_difference(&self, other: &SparseBoolField) -> SparseBoolField {
+        self.difference(other).union(&other.difference(self))
+    }
+
+    /// Field with every point moved by `offset`.
+    pub fn translate(&self, offset: Offset) -> SparseBoolField {
+        self.iter().map(|p| p + offset).collect()
+    }
+
+    /// Field mirrored across the vertical line through `x`.
+    pub fn reflect_x(&self, x: i32) -> SparseBoolField {
+        self.iter().map(|p| Point::new(2 * x - p.x, p.y)).collect()
+    }
+
+    /// Field mirrored across the horizontal line through `y`.
+    pub fn reflect_y(&self, y: i32) -> SparseBoolField {
+        self.iter().map(|p| Point::new(p.x, 2 * y - p.y)).collect()
+    }
+
+    /// Field with every point moved by `symmetry` around `center`. Unlike
+    /// [transform](SparseBoolField::transform), the bounds move too.
+    pub fn transform_around(&self, symmetry: Symmetry, center: Point) -> SparseBoolField {
+        self.iter()
+            .map(|p| symmetry.apply(p - center) + center)
+            .collect()
+    }
+
+    /// Field with all points of `rect` set.
+    pub fn from_rect(rect: &Rect) -> SparseBoolField {
+        rect.points().collect()
+    }
+}
+
+impl Extend<Point> for SparseBoolField {
+    fn extend<I: IntoIterator<Item = Point>>(&mut self, points: I) {
+        for point in points {
+            self.set(&point);
+        }
+    }
+}
+
+impl FromIterator<Point> for SparseBoolField {
+    fn from_iter<I: IntoIterator<Item = Point>>(points: I) -> Self {
+        let mut result = SparseBoolField::new();
+        result.extend(points);
+        result
+    }
+}
+
+impl BitOr for &SparseBoolField {
+    type Output = SparseBoolField;
+
+    fn bitor(self, other: &SparseBoolField) -> SparseBoolField {
+        self.union(other)
+    }
+}
+
+impl BitAnd for &SparseBoolField {
+    type Output = SparseBoolField;
+
+    fn bitand(self, other: &SparseBoolField) -> SparseBoolField {
+        self.intersection(other)
+    }
+}
+
+impl Sub for &SparseBoolField {
+    type Output = SparseBoolField;
+
+    fn sub(self, other: &SparseBoolField) -> SparseBoolField {
+        self.difference(other)
+    }
+}
+
+impl BitXor for &SparseBoolField {
+    type Output = SparseBoolField;
+
+    fn bitxor(self, other: &SparseBoolField) -> SparseBoolField {
+        self.symmetric_difference(other)
+    }
+}
+
+/// Fields are equal if they have the same points set.
+impl PartialEq for SparseBoolField {
+    fn eq(&self, other: &Self) -> bool {
+        self.points == other.points
     }
 }
 
+impl Eq for SparseBoolField {}
+
 impl fmt::Display for SparseBoolField {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let (top_left, bottom_right) = self.bounds();
-
-        for y in top_left.y..=bottom_right.y {
-            for x in top_left.x..=bottom_right.x {
-                write!(f, "{}", if self.get(&Point { x, y }) { "#" } else { "." })?;
+        let bounds = match self.bounds() {
+            Some(bounds) => bounds,
+            None => return Ok(()),
+        };
+        for point in bounds.points() {
+            write!(f, "{}", if self.get(&point) { "#" } else { "." })?;
+            if point.x == bounds.max.x {
+                writeln!(f)?;
             }
-            writeln!(f, "")?;
         }
         Ok(())
     }
 }
+
+/// Bounding box of a set of points that is kept up to date as points are
+/// added, and recomputed only when a point on its edge is removed.
+#[derive(Clone, Debug, Default)]
+struct BoundsTracker {
+    rect: Cell<Option<Rect>>,
+    stale: Cell<bool>,
+}
+
+impl BoundsTracker {
+    fn add(&mut self, point: Point) {
+        if !self.stale.get() {
+            let point = Rect::new(point, point);
+            let rect = self.rect.get().map_or(point, |rect| rect.union(&point));
+            self.rect.set(Some(rect));
+        }
+    }
+
+    fn remove(&mut self, point: Point) {
+        if let Some(rect) = self.rect.get() {
+            let on_edge = point.x == rect.min.x
+                || point.x == rect.max.x
+                || point.y == rect.min.y
+                || point.y == rect.max.y;
+            if on_edge {
+                self.stale.set(true);
+            }
+        }
+    }
+
+    /// Bounds of `points`, which are only iterated if the bounds are stale.
+    fn get<I: Iterator<Item = Point>>(&self, points: impl FnOnce() -> I) -> Option<Rect> {
+        if self.stale.replace(false) {
+            self.rect.set(Rect::from_points(points()));
+        }
+        self.rect.get()
+    }
+}
+
+/// Values of points on an infinite grid, where points that were never set
+/// have the background value.
+#[derive(Clone, Debug)]
+pub struct SparseField<T> {
+    cells: HashMap<Point, T>,
+    background: T,
+    bounds: BoundsTracker,
+}
+
+impl<T> SparseField<T> {
+    pub fn new(background: T) -> SparseField<T> {
+        SparseField {
+            cells: HashMap::new(),
+            background,
+            bounds: BoundsTracker::default(),
+        }
+    }
+
+    /// Value of the points that are not stored.
+    pub fn background(&self) -> &T {
+        &self.background
+    }
+
+    pub fn set_background(&mut self, background: T) {
+        self.background = background;
+    }
+
+    /// Value at `point`, the background if it is not stored.
+    pub fn get(&self, point: &Point) -> &T {
+        self.cells.get(point).unwrap_or(&self.background)
+    }
+
+    /// Stores `value` at `point`, returns the value stored before.
+    pub fn set(&mut self, point: &Point, value: T) -> Option<T> {
+        self.bounds.add(*point);
+        self.cells.insert(*point, value)
+    }
+
+    /// Removes the value at `point`, which gets the background value.
+    pub fn remove(&mut self, point: &Point) -> Option<T> {
+        let removed = self.cells.remove(point);
+        if removed.is_some() {
+            self.bounds.remove(*point);
+        }
+        removed
+    }
+
+    /// Number of stored points.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Stored points and their values, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().map(|(p, v)| (*p, v))
+    }
+
+    /// Smallest rectangle containing all stored points, `None` if there are
+    /// none.
+    pub fn bounds(&self) -> Option<Rect> {
+        self.bounds.get(|| self.cells.keys().copied())
+    }
+
+    /// Field with `f` applied to every stored value and the background.
+    pub fn map<U>(&self, mut f: impl FnMut(&T) -> U) -> SparseField<U> {
+        SparseField {
+            cells: self.cells.iter().map(|(p, v)| (*p, f(v))).collect(),
+            background: f(&self.background),
+            bounds: self.bounds.clone(),
+        }
+    }
+}
+
+impl<T: Clone + PartialEq> SparseField<T> {
+    /// Field with the values of `field` moved to start at `origin`, only
+    /// those that differ from `background` are stored.
+    pub fn from_vec_field(field: &VecField<T>, origin: Point, background: T) -> SparseField<T> {
+        let mut result = SparseField::new(background);
+        for (point, value) in field.iter() {
+            if *value != result.background {
+                result.set(&(point + origin), value.clone());
+            }
+        }
+        result
+    }
+
+    /// Values inside the bounds as a [VecField], with the position of its
+    /// `0,0`.
+    pub fn to_vec_field(&self) -> (Point, VecField<T>) {
+        match self.bounds() {
+            Some(bounds) => (
+                bounds.min,
+                VecField::from_fn(bounds.width(), bounds.height(), |p| {
+                    self.get(&(p + bounds.min)).clone()
+                }),
+            ),
+            None => (
+                Point::default(),
+                VecField::new(0, 0, self.background.clone()),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transform_test() {
+        let field = SparseBoolField::from_map("##.\n..#\n", '#');
+        let moved: SparseBoolField = field.iter().map(|p| p + Offset::new(5, -3)).collect();
+        assert_eq!(moved.rotate(1).to_string(), ".#\n.#\n#.\n");
+        assert_eq!(moved.rotate(1).bounds().unwrap().min, Point::new(5, -3));
+        assert_eq!(moved.flip_vertical().to_string(), "..#\n##.\n");
+        assert_eq!(moved.transpose().to_string(), "#.\n#.\n.#\n");
+        assert_eq!(moved.symmetries().count(), 8);
+        assert_eq!(
+            field.crop(&Rect::new(Point::new(1, 0), Point::new(2, 0))),
+            [Point::new(1, 0)].into_iter().collect()
+        );
+        assert_eq!(
+            field.pad(1, true).to_string(),
+            "#####\n###.#\n#..##\n#####\n"
+        );
+        assert_eq!(field.pad(1, false), field);
+    }
+
+    #[test]
+    fn set_algebra_test() {
+        let a = SparseBoolField::from_map("##.\n.#.\n", '#');
+        let b = SparseBoolField::from_map(".##\n..#\n", '#');
+        assert_eq!((&a | &b).to_string(), "###\n.##\n");
+        assert_eq!((&a & &b).to_string(), "#\n");
+        assert_eq!((&a & &b).iter().collect::<Vec<_>>(), vec![Point::new(1, 0)]);
+        assert_eq!((&a - &b).to_string(), "#.\n.#\n");
+        assert_eq!((&a ^ &b).to_string(), "#.#\n.##\n");
+        assert!((&a - &a).is_empty());
+        assert_eq!(
+            a.symmetric_difference(&b),
+            a.union(&b).difference(&a.intersection(&b))
+        );
+    }
+
+    #[test]
+    fn move_test() {
+        let field = SparseBoolField::from_map("##.\n..#\n", '#');
+        let moved = field.translate(Offset::new(-4, 2));
+        assert_eq!(moved.bounds().unwrap().min, Point::new(-4, 2));
+        assert_eq!(moved.to_string(), field.to_string());
+        assert_eq!(moved.translate(Offset::new(4, -2)), field);
+
+        let mirrored = field.reflect_x(3);
+        assert_eq!(mirrored.bounds().unwrap().min, Point::new(4, 0));
+        assert_eq!(mirrored.to_string(), ".##\n#..\n");
+        assert_eq!(mirrored.reflect_x(3), field);
+        assert_eq!(field.reflect_y(0).to_string(), "..#\n##.\n");
+        assert!(field.reflect_y(0).get(&Point::new(2, -1)));
+
+        let rotated = field.transform_around(Symmetry::ROTATE_90, Point::default());
+        assert_eq!(rotated.bounds().unwrap().min, Point::new(-1, 0));
+        assert_eq!(rotated.to_string(), field.rotate(1).to_string());
+        assert_eq!(
+            field.transform_around(Symmetry::ROTATE_180, Point::new(1, 0)),
+            field.rotate(2).translate(Offset::new(0, -1))
+        );
+    }
+
+    #[test]
+    fn crop_test() {
+        let map = "#..#\n.##.\n#..#\n";
+        let sparse = SparseBoolField::from_map(map, '#');
+        let dense = VecField::parse(map, "'#' or '.'", |ch| Some(ch == '#')).unwrap();
+        for rect in [
+            Rect::new(Point::new(1, 1), Point::new(3, 2)),
+            Rect::new(Point::new(-2, -1), Point::new(1, 1)),
+            Rect::new(Point::new(3, 2), Point::new(7, 7)),
+            Rect::new(Point::new(5, 0), Point::new(6, 1)),
+        ] {
+            let (origin, cropped) = dense.crop(&rect);
+            let cropped: SparseBoolField = cropped
+                .iter()
+                .filter(|(_, set)| **set)
+                .map(|(p, _)| p + origin)
+                .collect();
+            assert_eq!(sparse.crop(&rect), cropped, "{:?}", rect);
+        }
+        assert_eq!(
+            sparse
+                .crop(&Rect::new(Point::new(1, 1), Point::new(3, 2)))
+                .bounds(),
+            Some(Rect::new(Point::new(1, 1), Point::new(3, 2)))
+        );
+    }
+
+    #[test]
+    fn bounds_test() {
+        let mut field = SparseBoolField::from_map("#..\n.#.\n..#\n", '#');
+        let bounds = |field: &SparseBoolField| field.bounds().map(|b| (b.min, b.max));
+        assert_eq!(bounds(&field), Some((Point::new(0, 0), Point::new(2, 2))));
+        field.clear(&Point::new(1, 1));
+        assert!(!field.bounds.stale.get());
+        field.clear(&Point::new(2, 2));
+        assert!(field.bounds.stale.get());
+        assert_eq!(bounds(&field), Some((Point::new(0, 0), Point::new(0, 0))));
+        field.set(&Point::new(-3, 4));
+        assert_eq!(bounds(&field), Some((Point::new(-3, 0), Point::new(0, 4))));
+        field.clear(&Point::new(-3, 4));
+        field.set(&Point::new(1, 1));
+        assert_eq!(bounds(&field), Some((Point::new(0, 0), Point::new(1, 1))));
+        field.clear(&Point::new(0, 0));
+        field.clear(&Point::new(1, 1));
+        assert_eq!(bounds(&field), None);
+        assert!(field.is_empty());
+    }
+
+    #[test]
+    fn sparse_field_test() {
+        let mut field = SparseField::new('.');
+        assert_eq!(field.bounds(), None);
+        field.set(&Point::new(2, -1), '#');
+        field.set(&Point::new(-1, 3), '#');
+        field.set(&Point::new(0, 0), '.');
+        assert_eq!(field.len(), 3);
+        assert_eq!(field.get(&Point::new(5, 5)), &'.');
+        assert_eq!(
+            field.bounds(),
+            Some(Rect::new(Point::new(-1, -1), Point::new(2, 3)))
+        );
+        assert_eq!(field.remove(&Point::new(-1, 3)), Some('#'));
+        assert_eq!(
+            field.bounds(),
+            Some(Rect::new(Point::new(0, -1), Point::new(2, 0)))
+        );
+        field.set_background('#');
+        assert_eq!(field.get(&Point::new(-1, 3)), &'#');
+
+        let lit = field.map(|v| *v == '#');
+        assert_eq!(lit.background(), &true);
+        let mut stored: Vec<(Point, bool)> = lit.iter().map(|(p, v)| (p, *v)).collect();
+        stored.sort();
+        assert_eq!(
+            stored,
+            vec![(Point::new(0, 0), false), (Point::new(2, -1), true)]
+        );
+    }
+
+    #[test]
+    fn vec_field_conversion_test() {
+        let image = VecField::parse("#..\n..#\n", "pixel", |ch| Some(ch == '#')).unwrap();
+        let field = SparseField::from_vec_field(&image, Point::new(10, 20), false);
+        assert_eq!(field.len(), 2);
+        assert!(*field.get(&Point::new(12, 21)));
+        let (origin, back) = field.to_vec_field();
+        assert_eq!(origin, Point::new(10, 20));
+        assert_eq!(back, image);
+
+        let (_, empty) = SparseField::new(0).to_vec_field();
+        assert_eq!((empty.width, empty.height), (0, 0));
+    }
+}
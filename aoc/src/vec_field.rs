@@ -1,33 +1,432 @@
+//! Dense rectangular grids stored row by row, with `0,0` in the top left
+//! corner.
+use super::error::AocError;
+use super::points::symmetry::Symmetry;
 use super::points::*;
+use std::fmt;
+use std::ops::{Index, IndexMut};
 
-pub struct VecField<T: Copy> {
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct VecField<T> {
     pub width: u32,
     pub height: u32,
     pub values: Vec<T>,
 }
 
-impl<T: Copy> VecField<T> {
-    pub fn get(&self, point: &Point) -> T {
-        self.values[(point.x + point.y * (self.width as i32)) as usize]
+impl<T> VecField<T> {
+    /// Field with every point set to `fill`.
+    pub fn new(width: u32, height: u32, fill: T) -> VecField<T>
+    where
+        T: Clone,
+    {
+        VecField {
+            width,
+            height,
+            values: vec![fill; width as usize * height as usize],
+        }
+    }
+
+    /// Field with the value of each point computed by `f`.
+    pub fn from_fn(width: u32, height: u32, f: impl FnMut(Point) -> T) -> VecField<T> {
+        VecField {
+            width,
+            height,
+            values: (0..height as i32)
+                .flat_map(|y| (0..width as i32).map(move |x| Point::new(x, y)))
+                .map(f)
+                .collect(),
+        }
+    }
+
+    /// Parses lines of equal length, each char into a value by `f`. `what`
+    /// names the expected chars in errors.
+    pub fn parse(
+        text: &str,
+        what: &str,
+        mut f: impl FnMut(char) -> Option<T>,
+    ) -> Result<VecField<T>, AocError> {
+        let mut values = Vec::new();
+        let mut height = 0;
+        let mut width = 0;
+        for line in text.lines() {
+            let start = values.len();
+            for (i, ch) in line.char_indices() {
+                values.push(f(ch).ok_or_else(|| {
+                    AocError::parse(
+                        text,
+                        &line[i..i + ch.len_utf8()],
+                        format!("expected {}", what),
+                    )
+                })?);
+            }
+            let row_width = (values.len() - start) as u32;
+            if height > 0 && row_width != width {
+                return Err(AocError::parse(
+                    text,
+                    line,
+                    format!("expected {} {}s", width, what),
+                ));
+            }
+            height += 1;
+            width = row_width;
+        }
+        Ok(VecField {
+            width,
+            height,
+            values,
+        })
+    }
+
+    pub fn contains(&self, point: &Point) -> bool {
+        point.x >= 0 && point.y >= 0 && point.x < self.width as i32 && point.y < self.height as i32
+    }
+
+    fn index_of(&self, point: &Point) -> Option<usize> {
+        self.contains(point)
+            .then(|| point.x as usize + point.y as usize * self.width as usize)
+    }
+
+    /// Value at `point`, `None` if the point is outside of the field.
+    pub fn get(&self, point: &Point) -> Option<&T> {
+        self.index_of(point).map(|i| &self.values[i])
+    }
+
+    pub fn get_mut(&mut self, point: &Point) -> Option<&mut T> {
+        self.index_of(point).map(|i| &mut self.values[i])
+    }
+
+    /// Sets the value at `point`, returns false if the point is outside of
+    /// the field.
+    pub fn set(&mut self, point: &Point, value: T) -> bool {
+        match self.get_mut(point) {
+            Some(v) => {
+                *v = value;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Rectangle of all points of the field, `None` if it has no points.
+    pub fn bounds(&self) -> Option<Rect> {
+        (self.width > 0 && self.height > 0).then(|| {
+            Rect::new(
+                Point::default(),
+                Point::new(self.width as i32 - 1, self.height as i32 - 1),
+            )
+        })
+    }
+
+    /// Neighbors of `point` inside the field.
+    pub fn neighbors(&self, point: Point, connectivity: Connectivity) -> Neighbors {
+        point.neighbors(
+            connectivity,
+            Topology::Bounded {
+                width: self.width,
+                height: self.height,
+            },
+        )
+    }
+
+    /// Points of the field in reading order.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width as i32;
+        (0..self.height as i32).flat_map(move |y| (0..width).map(move |x| Point::new(x, y)))
+    }
+
+    /// Points and their values in reading order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.values.iter())
+    }
+
+    /// Values of the row `y`, `None` if it is outside of the field.
+    pub fn row(&self, y: u32) -> Option<&[T]> {
+        let start = y as usize * self.width as usize;
+        (y < self.height).then(|| &self.values[start..start + self.width as usize])
+    }
+
+    /// Rows from top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).filter_map(|y| self.row(y))
+    }
+
+    /// Values of the column `x` from top to bottom, `None` if it is outside
+    /// of the field.
+    pub fn column(&self, x: u32) -> Option<impl Iterator<Item = &T>> {
+        (x < self.width).then(|| {
+            self.values
+                .iter()
+                .skip(x as usize)
+                .step_by(self.width as usize)
+        })
+    }
+
+    /// Columns from left to right.
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).filter_map(|x| self.column(x))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> VecField<U> {
+        VecField {
+            width: self.width,
+            height: self.height,
+            values: self.values.iter().map(f).collect(),
+        }
+    }
+
+    /// Field of `f` applied to the values at the same points of both fields,
+    /// which must have the same size.
+    pub fn zip_with<U, V>(
+        &self,
+        other: &VecField<U>,
+        mut f: impl FnMut(&T, &U) -> V,
+    ) -> VecField<V> {
+        assert_eq!(
+            (self.width, self.height),
+            (other.width, other.height),
+            "Fields differ in size"
+        );
+        VecField {
+            width: self.width,
+            height: self.height,
+            values: self
+                .values
+                .iter()
+                .zip(other.values.iter())
+                .map(|(a, b)| f(a, b))
+                .collect(),
+        }
+    }
+}
+
+impl<T: Clone> VecField<T> {
+    /// Field with each point moved by `symmetry`, see
+    /// [Symmetry::apply_to_rect].
+    pub fn transform(&self, symmetry: Symmetry) -> VecField<T> {
+        let (width, height) = if symmetry.swaps_axes() {
+            (self.height, self.width)
+        } else {
+            (self.width, self.height)
+        };
+        let mut values = self.values.clone();
+        if let Some(bounds) = self.bounds() {
+            for (point, value) in self.iter() {
+                let image = symmetry.apply_in(&bounds, point);
+                values[image.x as usize + image.y as usize * width as usize] = value.clone();
+            }
+        }
+        VecField {
+            width,
+            height,
+            values,
+        }
+    }
+
+    /// Field rotated clockwise by `quarter_turns`, counterclockwise if
+    /// negative.
+    pub fn rotate(&self, quarter_turns: i32) -> VecField<T> {
+        self.transform(Symmetry::rotation(quarter_turns))
+    }
+
+    /// Field with left and right swapped.
+    pub fn flip_horizontal(&self) -> VecField<T> {
+        self.transform(Symmetry::FLIP_HORIZONTAL)
+    }
+
+    /// Field with top and bottom swapped.
+    pub fn flip_vertical(&self) -> VecField<T> {
+        self.transform(Symmetry::FLIP_VERTICAL)
+    }
+
+    pub fn transpose(&self) -> VecField<T> {
+        self.transform(Symmetry::TRANSPOSE)
+    }
+
+    /// The field in each of [Symmetry::ALL].
+    pub fn symmetries(&self) -> impl Iterator<Item = VecField<T>> + '_ {
+        Symmetry::ALL.into_iter().map(|s| self.transform(s))
+    }
+
+    /// Part of the field inside `rect`, see [Rect], with the position of its
+    /// `0,0` in this field.
+    pub fn crop(&self, rect: &Rect) -> (Point, VecField<T>) {
+        match self.bounds().and_then(|bounds| rect.intersection(&bounds)) {
+            Some(rect) => (
+                rect.min,
+                VecField::from_fn(rect.width(), rect.height(), |p| self[p + rect.min].clone()),
+            ),
+            None => (
+                Point::default(),
+                VecField {
+                    width: 0,
+                    height: 0,
+                    values: Vec::new(),
+                },
+            ),
+        }
+    }
+
+    /// Field surrounded by `margin` points of `fill` on every side.
+    pub fn pad(&self, margin: u32, fill: T) -> VecField<T> {
+        let offset = Offset::new(margin as i32, margin as i32);
+        let mut result = VecField::new(self.width + 2 * margin, self.height + 2 * margin, fill);
+        for (point, value) in self.iter() {
+            result[point + offset] = value.clone();
+        }
+        result
+    }
+}
+
+impl<T> Index<Point> for VecField<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        self.get(&point)
+            .unwrap_or_else(|| panic!("{} is outside of the field", point))
+    }
+}
+
+impl<T> IndexMut<Point> for VecField<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        self.get_mut(&point)
+            .unwrap_or_else(|| panic!("{} is outside of the field", point))
     }
 }
 
-pub fn parse_matrix(text: &str) -> VecField<u8> {
-    let mut values: Vec<u8> = Vec::new();
-    let mut height = 0;
-    let mut width = 0;
-    for line in text.lines() {
-        height += 1;
-        width = 0;
-        for char in line.chars() {
-            values.push(char as u8 - ('0' as u8));
-            width += 1;
+/// Rows of values without separators.
+impl<T: fmt::Display> fmt::Display for VecField<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for value in row {
+                write!(f, "{}", value)?;
+            }
+            writeln!(f)?;
         }
+        Ok(())
+    }
+}
+
+/// Parses lines of digits.
+pub fn parse_matrix(text: &str) -> Result<VecField<u8>, AocError> {
+    VecField::parse(text, "digit", |ch| ch.to_digit(10).map(|d| d as u8))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn access_test() {
+        let mut field = VecField::new(3, 2, 0);
+        assert!(field.set(&Point::new(2, 1), 5));
+        assert!(!field.set(&Point::new(3, 0), 5));
+        field[Point::new(1, 0)] = 7;
+        assert_eq!(field.values, vec![0, 7, 0, 0, 0, 5]);
+        assert_eq!(field.get(&Point::new(2, 1)), Some(&5));
+        assert_eq!(field.get(&Point::new(-1, 0)), None);
+        assert_eq!(field[Point::new(1, 0)], 7);
+        assert_eq!(field.bounds().unwrap().area(), 6);
+        let empty = VecField::new(3, 0, 0);
+        assert_eq!(empty.bounds(), None);
+        assert_eq!(empty.points().count(), 0);
+        assert_eq!(
+            field.neighbors(Point::new(0, 0), Connectivity::All).count(),
+            3
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "3,0 is outside of the field")]
+    fn index_test() {
+        let field = VecField::new(3, 2, 0);
+        let _ = field[Point::new(3, 0)];
+    }
+
+    #[test]
+    fn iter_test() {
+        let field = VecField::from_fn(3, 2, |p| p.x + 10 * p.y);
+        assert_eq!(field.values, vec![0, 1, 2, 10, 11, 12]);
+        assert_eq!(
+            field.rows().collect::<Vec<_>>(),
+            vec![&[0, 1, 2], &[10, 11, 12]]
+        );
+        assert_eq!(
+            field.column(1).unwrap().copied().collect::<Vec<_>>(),
+            vec![1, 11]
+        );
+        assert!(field.column(3).is_none());
+        assert!(field.row(2).is_none());
+        assert_eq!(field.columns().count(), 3);
+        assert_eq!(field.iter().last(), Some((Point::new(2, 1), &12)));
+        assert_eq!(field.points().nth(3), Some(Point::new(0, 1)));
+        assert_eq!(VecField::new(0, 0, 0).points().count(), 0);
+
+        let doubled = field.map(|v| v * 2);
+        assert_eq!(doubled[Point::new(2, 1)], 24);
+        let sum = field.zip_with(&doubled, |a, b| a + b);
+        assert_eq!(sum.row(1), Some(&[30, 33, 36][..]));
+    }
+
+    #[test]
+    fn transform_test() {
+        let field = parse_matrix("123\n456\n").unwrap();
+        assert_eq!(field.rotate(1).to_string(), "41\n52\n63\n");
+        assert_eq!(field.rotate(-1).to_string(), "36\n25\n14\n");
+        assert_eq!(field.rotate(2), field.flip_horizontal().flip_vertical());
+        assert_eq!(field.flip_horizontal().to_string(), "321\n654\n");
+        assert_eq!(field.flip_vertical().to_string(), "456\n123\n");
+        assert_eq!(field.transpose().to_string(), "14\n25\n36\n");
+        let all: Vec<String> = field.symmetries().map(|f| f.to_string()).collect();
+        assert_eq!(all.len(), 8);
+        assert!(all
+            .iter()
+            .all(|a| all.iter().filter(|b| a == *b).count() == 1));
+    }
+
+    #[test]
+    fn crop_pad_test() {
+        let field = parse_matrix("123\n456\n").unwrap();
+        let padded = field.pad(1, 0);
+        assert_eq!(padded.to_string(), "00000\n01230\n04560\n00000\n");
+        assert_eq!(
+            padded.crop(&Rect::new(Point::new(1, 1), Point::new(3, 2))),
+            (Point::new(1, 1), field.clone())
+        );
+        let (origin, cropped) = field.crop(&Rect::new(Point::new(-5, 1), Point::new(1, 7)));
+        assert_eq!(origin, Point::new(0, 1));
+        assert_eq!(cropped.to_string(), "45\n");
+        let (_, empty) = field.crop(&Rect::new(Point::new(5, 5), Point::new(6, 6)));
+        assert_eq!((empty.width, empty.height), (0, 0));
+        assert_eq!(empty.pad(1, 0).to_string(), "00\n00\n");
+
+        let flat = VecField::new(3, 0, 0);
+        let rect = Rect::new(Point::new(0, 0), Point::new(1, 1));
+        assert_eq!(flat.crop(&rect).1.values.len(), 0);
+        assert_eq!(flat.pad(1, 7).to_string(), "77777\n77777\n");
+        let transposed = flat.transpose();
+        assert_eq!((transposed.width, transposed.height), (0, 3));
     }
 
-    VecField {
-        height,
-        width,
-        values,
+    #[test]
+    fn parse_test() {
+        let field = VecField::parse("#.\n.#\n", "'#' or '.'", |ch| match ch {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+        .unwrap();
+        assert_eq!(field.values, vec![true, false, false, true]);
+        assert_eq!(
+            field.map(|v| if *v { '#' } else { '.' }).to_string(),
+            "#.\n.#\n"
+        );
+        assert_eq!(
+            parse_matrix("12\n3x").unwrap_err().to_string(),
+            "line 2 col 2: expected digit, found \"x\""
+        );
+        assert_eq!(
+            parse_matrix("12\n345").unwrap_err().to_string(),
+            "line 2 col 1: expected 2 digits, found \"345\""
+        );
     }
 }